@@ -0,0 +1,5 @@
+mod receive_message;
+pub use receive_message::*;
+
+mod reclaim_event_account;
+pub use reclaim_event_account::*;
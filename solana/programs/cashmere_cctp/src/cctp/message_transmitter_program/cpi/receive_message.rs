@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+/// Account context to invoke [receive_message].
+pub struct ReceiveMessage<'info> {
+    //#[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+
+    /// Signer. Must be the message's destination caller, if one was set.
+    //#[account(signer)]
+    pub caller: AccountInfo<'info>,
+
+    /// Seeds must be \["message_transmitter_authority", receiver\] (CCTP Message Transmitter
+    /// program).
+    pub authority_pda: AccountInfo<'info>,
+
+    /// Seeds must be \["message_transmitter"\] (CCTP Message Transmitter program).
+    pub message_transmitter: AccountInfo<'info>,
+
+    /// Mutable. Seeds must be \["used_nonces", source_domain.to_string(), first_nonce\] (CCTP
+    /// Message Transmitter program).
+    //#[account(mut)]
+    pub used_nonces: AccountInfo<'info>,
+
+    /// Program handling the message body, e.g. the CCTP Token Messenger Minter program.
+    pub receiver: AccountInfo<'info>,
+
+    pub system_program: AccountInfo<'info>,
+
+    /// Seeds must be \["__event_authority"\] (CCTP Message Transmitter program).
+    pub event_authority: AccountInfo<'info>,
+
+    /// CCTP Message Transmitter program.
+    pub program: AccountInfo<'info>,
+}
+
+/// Parameters to invoke [receive_message].
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ReceiveMessageParams {
+    pub message: Vec<u8>,
+    pub attestation: Vec<u8>,
+}
+
+/// CPI call to invoke the CCTP Message Transmitter program to receive a message. Accounts required
+/// by the receiver go in the context's remaining accounts.
+pub fn receive_message<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, ReceiveMessage<'info>>,
+    args: ReceiveMessageParams,
+) -> Result<()> {
+    const ANCHOR_IX_SELECTOR: [u8; 8] = [38, 144, 127, 225, 31, 225, 238, 25];
+
+    crate::cctp::invoke_anchor_ix(
+        crate::cctp::MESSAGE_TRANSMITTER_PROGRAM_ID,
+        ANCHOR_IX_SELECTOR,
+        ctx,
+        args,
+    )
+}
+
+impl<'info> ToAccountMetas for ReceiveMessage<'info> {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.payer.key(), true),
+            AccountMeta::new_readonly(self.caller.key(), true),
+            AccountMeta::new_readonly(self.authority_pda.key(), false),
+            AccountMeta::new_readonly(self.message_transmitter.key(), false),
+            AccountMeta::new(self.used_nonces.key(), false),
+            AccountMeta::new_readonly(self.receiver.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+            AccountMeta::new_readonly(self.event_authority.key(), false),
+            AccountMeta::new_readonly(self.program.key(), false),
+        ]
+    }
+}
+
+impl<'info> ToAccountInfos<'info> for ReceiveMessage<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.payer.clone(),
+            self.caller.clone(),
+            self.authority_pda.clone(),
+            self.message_transmitter.clone(),
+            self.used_nonces.clone(),
+            self.receiver.clone(),
+            self.system_program.clone(),
+            self.event_authority.clone(),
+            self.program.clone(),
+        ]
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// Account context to invoke [reclaim_event_account].
+pub struct ReclaimEventAccount<'info> {
+    /// Mutable signer. Must be the `rent_payer` recorded in `message_sent_event_data`; receives the
+    /// account's lamports.
+    //#[account(mut, signer)]
+    pub payee: AccountInfo<'info>,
+
+    /// Mutable. Seeds must be \["message_transmitter"\] (CCTP Message Transmitter program).
+    //#[account(mut)]
+    pub message_transmitter: AccountInfo<'info>,
+
+    /// Mutable. The attested message account to close.
+    //#[account(mut)]
+    pub message_sent_event_data: AccountInfo<'info>,
+}
+
+/// Parameters to invoke [reclaim_event_account].
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ReclaimEventAccountParams {
+    /// Circle attestation of the message stored in `message_sent_event_data`.
+    pub attestation: Vec<u8>,
+}
+
+/// CPI call to invoke the CCTP Message Transmitter program to close an attested message account
+/// and return its rent to the payee.
+pub fn reclaim_event_account<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, ReclaimEventAccount<'info>>,
+    args: ReclaimEventAccountParams,
+) -> Result<()> {
+    const ANCHOR_IX_SELECTOR: [u8; 8] = [94, 198, 180, 159, 131, 236, 15, 174];
+
+    crate::cctp::invoke_anchor_ix(
+        crate::cctp::MESSAGE_TRANSMITTER_PROGRAM_ID,
+        ANCHOR_IX_SELECTOR,
+        ctx,
+        args,
+    )
+}
+
+impl<'info> ToAccountMetas for ReclaimEventAccount<'info> {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.payee.key(), true),
+            AccountMeta::new(self.message_transmitter.key(), false),
+            AccountMeta::new(self.message_sent_event_data.key(), false),
+        ]
+    }
+}
+
+impl<'info> ToAccountInfos<'info> for ReclaimEventAccount<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.payee.clone(),
+            self.message_transmitter.clone(),
+            self.message_sent_event_data.clone(),
+        ]
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MessageReceived {
+    pub caller: Pubkey,
+    pub source_domain: u32,
+    pub nonce: u64,
+    pub sender: Pubkey,
+    pub message_body: Vec<u8>,
+}
+
+crate::cctp::impl_anchor_event!(MessageReceived, [231, 68, 47, 77, 173, 241, 157, 166]);
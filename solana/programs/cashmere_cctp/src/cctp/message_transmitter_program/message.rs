@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+/// Read-only view over a CCTP message as stored in [MessageSent](super::MessageSent). All integers
+/// are big-endian.
+///
+/// ```text
+/// version             u32      0..4
+/// source_domain       u32      4..8
+/// destination_domain  u32      8..12
+/// nonce               u64      12..20
+/// sender              [u8; 32] 20..52
+/// recipient           [u8; 32] 52..84
+/// destination_caller  [u8; 32] 84..116
+/// message_body        bytes    116..
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Message<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Message<'a> {
+    pub const HEADER_LEN: usize = 116;
+
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        if data.len() < Self::HEADER_LEN {
+            return None;
+        }
+        Some(Self { data })
+    }
+
+    pub fn version(&self) -> u32 {
+        u32::from_be_bytes(self.data[0..4].try_into().unwrap())
+    }
+
+    pub fn source_domain(&self) -> u32 {
+        u32::from_be_bytes(self.data[4..8].try_into().unwrap())
+    }
+
+    pub fn destination_domain(&self) -> u32 {
+        u32::from_be_bytes(self.data[8..12].try_into().unwrap())
+    }
+
+    pub fn nonce(&self) -> u64 {
+        u64::from_be_bytes(self.data[12..20].try_into().unwrap())
+    }
+
+    pub fn sender(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[20..52].try_into().unwrap())
+    }
+
+    pub fn recipient(&self) -> [u8; 32] {
+        self.data[52..84].try_into().unwrap()
+    }
+
+    pub fn destination_caller(&self) -> [u8; 32] {
+        self.data[84..116].try_into().unwrap()
+    }
+
+    pub fn message_body(&self) -> &'a [u8] {
+        &self.data[Self::HEADER_LEN..]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+}
@@ -0,0 +1,10 @@
+pub mod cpi;
+pub mod events;
+
+mod message;
+pub use message::*;
+
+mod state;
+pub use state::*;
+
+anchor_lang::declare_id!(crate::cctp::MESSAGE_TRANSMITTER_PROGRAM_ID);
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Account created by the Message Transmitter program for every outgoing message. Circle's
+/// attestation service reads the message from here.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MessageSent {
+    pub rent_payer: Pubkey,
+    pub message: Vec<u8>,
+}
+
+crate::cctp::impl_anchor_account_readonly!(
+    MessageSent,
+    crate::cctp::MESSAGE_TRANSMITTER_PROGRAM_ID,
+    [131, 100, 133, 56, 166, 225, 151, 60]
+);
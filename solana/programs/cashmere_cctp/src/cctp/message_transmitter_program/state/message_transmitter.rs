@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MessageTransmitter {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+    pub attester_manager: Pubkey,
+    pub pauser: Pubkey,
+    pub paused: bool,
+    pub local_domain: u32,
+    pub version: u32,
+    pub signature_threshold: u32,
+    pub enabled_attesters: Vec<Pubkey>,
+    pub max_message_body_size: u64,
+    pub next_available_nonce: u64,
+}
+
+impl MessageTransmitter {
+    pub const SEED_PREFIX: &'static [u8] = b"message_transmitter";
+}
+
+crate::cctp::impl_anchor_account_readonly!(
+    MessageTransmitter,
+    crate::cctp::MESSAGE_TRANSMITTER_PROGRAM_ID,
+    [71, 40, 180, 142, 19, 203, 35, 252]
+);
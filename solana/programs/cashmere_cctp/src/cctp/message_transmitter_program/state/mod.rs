@@ -0,0 +1,5 @@
+mod message_sent;
+pub use message_sent::*;
+
+mod message_transmitter;
+pub use message_transmitter::*;
@@ -1,12 +1,25 @@
+pub mod message_transmitter_program;
+pub mod pda;
 pub mod token_messenger_minter_program;
 
-use anchor_lang::solana_program::{pubkey, pubkey::Pubkey};
+use crate::errors::CctpError;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        self,
+        program::get_return_data,
+        pubkey,
+    },
+};
 
 pub const MESSAGE_TRANSMITTER_PROGRAM_ID: Pubkey =
     pubkey!("CCTPmbSD7gX1bxKPAmg77w8oFzNFpaQiQUWD43TKaecd");
 pub const TOKEN_MESSENGER_MINTER_PROGRAM_ID: Pubkey =
     pubkey!("CCTPiPYPc6AsJuwueEnWgSgucamXDZwBd53dQ11YiKX3");
 
+/// CCTP domain of Solana.
+pub const LOCAL_DOMAIN: u32 = 5;
+
 #[macro_export]
 macro_rules! impl_anchor_account_readonly {
     ($anchor_acc:ty, $owner:expr, $disc:expr) => {
@@ -42,3 +55,75 @@ macro_rules! impl_anchor_account_readonly {
 }
 
 pub use impl_anchor_account_readonly;
+
+#[macro_export]
+macro_rules! impl_anchor_event {
+    ($anchor_event:ty, $disc:expr) => {
+        impl anchor_lang::Discriminator for $anchor_event {
+            const DISCRIMINATOR: &'static [u8] = &$disc;
+        }
+
+        impl $anchor_event {
+            /// Decodes the event from `emit_cpi!` instruction data or a base64-decoded
+            /// `Program data:` log, both of which start with the event discriminator.
+            pub fn try_from_event_data(data: &[u8]) -> anchor_lang::Result<Self> {
+                let data = data
+                    .strip_prefix(anchor_lang::event::EVENT_IX_TAG_LE)
+                    .unwrap_or(data);
+                require!(
+                    data.len() >= 8
+                        && data[..8] == *<Self as anchor_lang::Discriminator>::DISCRIMINATOR,
+                    anchor_lang::error::ErrorCode::InstructionDidNotDeserialize,
+                );
+                Self::deserialize(&mut &data[8..]).map_err(Into::into)
+            }
+        }
+    };
+}
+
+pub use impl_anchor_event;
+
+/// Invokes an Anchor instruction of a CCTP program.
+pub(crate) fn invoke_anchor_ix<'info, T, A>(
+    program_id: Pubkey,
+    selector: [u8; 8],
+    ctx: CpiContext<'_, '_, '_, 'info, T>,
+    args: A,
+) -> Result<()>
+where
+    T: ToAccountMetas + ToAccountInfos<'info>,
+    A: AnchorSerialize,
+{
+    solana_program::program::invoke_signed(
+        &solana_program::instruction::Instruction {
+            program_id,
+            accounts: ctx.to_account_metas(None),
+            data: (selector, args).try_to_vec()?,
+        },
+        &ctx.to_account_infos(),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Invokes an Anchor instruction of a CCTP program and decodes the value it set as return data.
+pub(crate) fn invoke_anchor_ix_with_return<'info, T, A, R>(
+    program_id: Pubkey,
+    selector: [u8; 8],
+    ctx: CpiContext<'_, '_, '_, 'info, T>,
+    args: A,
+) -> Result<R>
+where
+    T: ToAccountMetas + ToAccountInfos<'info>,
+    A: AnchorSerialize,
+    R: AnchorDeserialize,
+{
+    invoke_anchor_ix(program_id, selector, ctx, args)?;
+
+    match get_return_data() {
+        Some((returned_by, data)) if returned_by == program_id => {
+            R::try_from_slice(&data).map_err(Into::into)
+        }
+        _ => Err(CctpError::MissingReturnData.into()),
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use super::TOKEN_MESSENGER_MINTER_PROGRAM_ID;
+
+/// \["message_transmitter"\] (CCTP Message Transmitter program).
+pub const MESSAGE_TRANSMITTER: Pubkey = pubkey!("BWrwSWjbikT3H7qHAkUEbLmwDQoB4ZDJ4wcSEhSPTZCu");
+
+/// \["token_messenger"\] (CCTP Token Messenger Minter program).
+pub const TOKEN_MESSENGER: Pubkey = pubkey!("Afgq3BHEfCE7d78D2XE9Bfyu2ieDqvE24xX8KDwreBms");
+
+/// \["token_minter"\] (CCTP Token Messenger Minter program).
+pub const TOKEN_MINTER: Pubkey = pubkey!("DBD8hAwLDRQkTsu6EqviaYNGKPnsAMmQonxf7AH8ZcFY");
+
+/// \["sender_authority"\] (CCTP Token Messenger Minter program).
+pub const TOKEN_MESSENGER_MINTER_SENDER_AUTHORITY: Pubkey = pubkey!("X5rMYSBWMqeWULSdDKXXATBjqk9AJF8odHpYJYeYA9H");
+
+/// \["__event_authority"\] (CCTP Token Messenger Minter program).
+pub const TOKEN_MESSENGER_MINTER_EVENT_AUTHORITY: Pubkey = pubkey!("CNfZLeeL4RUxwfPnjA3tLiQt4y43jp4V7bMpga673jf9");
+
+/// \["remote_token_messenger", remote_domain.to_string()\] (CCTP Token Messenger Minter program)
+/// of domains 0 to 10, indexed by domain.
+const REMOTE_TOKEN_MESSENGERS: [Pubkey; 11] = [
+    pubkey!("Hazwi3jFQtLKc2ughi7HFXPkpDeso7DQaMR9Ks4afh3j"),
+    pubkey!("FWFDZqvQx3PVr8Y92d8vp5EQFpZNVPA8G42d9KfkhPk5"),
+    pubkey!("AcUS9yaMAyyC12RCwMnkJBpbkzQfa5d3ErSYdACBapJw"),
+    pubkey!("REzxi9nX3Eqseha5fBiaJhTC6SFJx4qJhP83U4UCrtc"),
+    pubkey!("3LQBc39CVMtAMN84LP38LeFUdrVWrRkrsi8gBuPW1dER"),
+    pubkey!("CCdEjr6KokJGw9E5boq6twyjozmfnymvHqoUmw8r3Wa1"),
+    pubkey!("BWyFzH6LsnmDAaDWbGsriQ9SiiKq1CF6pbH4Ye3kzSBV"),
+    pubkey!("HENV19bfgVUXtvnCNxYnVDKhDjer6i34xU5sKrcbgdyt"),
+    pubkey!("GhGnraU42p2rcLgwfUDxkrQsbJx73wgA4ZU9gDTizZhU"),
+    pubkey!("3CTbq3SF9gekPHiJwLsyivfVbuaRFAQwQ6eQgtNy8nP1"),
+    pubkey!("GK35Aa8yB3g4y3LQQA5V65cBTAnrHdc1fn5MPhguxhMK"),
+];
+
+/// \["remote_token_messenger", remote_domain.to_string()\] (CCTP Token Messenger Minter program).
+/// Only derived for domains past the lookup table.
+pub fn remote_token_messenger(remote_domain: u32) -> Pubkey {
+    match REMOTE_TOKEN_MESSENGERS.get(remote_domain as usize) {
+        Some(remote_token_messenger) => *remote_token_messenger,
+        None => Pubkey::find_program_address(
+            &[b"remote_token_messenger", remote_domain.to_string().as_bytes()],
+            &TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+        ).0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cctp::MESSAGE_TRANSMITTER_PROGRAM_ID;
+
+    fn derive(seed: &[u8], program_id: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[seed], program_id).0
+    }
+
+    #[test]
+    fn constants_match_derivation() {
+        assert_eq!(MESSAGE_TRANSMITTER, derive(b"message_transmitter", &MESSAGE_TRANSMITTER_PROGRAM_ID));
+        assert_eq!(TOKEN_MESSENGER, derive(b"token_messenger", &TOKEN_MESSENGER_MINTER_PROGRAM_ID));
+        assert_eq!(TOKEN_MINTER, derive(b"token_minter", &TOKEN_MESSENGER_MINTER_PROGRAM_ID));
+        assert_eq!(TOKEN_MESSENGER_MINTER_SENDER_AUTHORITY, derive(b"sender_authority", &TOKEN_MESSENGER_MINTER_PROGRAM_ID));
+        assert_eq!(TOKEN_MESSENGER_MINTER_EVENT_AUTHORITY, derive(b"__event_authority", &TOKEN_MESSENGER_MINTER_PROGRAM_ID));
+    }
+
+    #[test]
+    fn remote_token_messengers_match_derivation() {
+        for domain in 0..=REMOTE_TOKEN_MESSENGERS.len() as u32 {
+            assert_eq!(
+                remote_token_messenger(domain),
+                derive_remote(domain),
+                "domain {domain}",
+            );
+        }
+    }
+
+    fn derive_remote(domain: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"remote_token_messenger", domain.to_string().as_bytes()],
+            &TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+        ).0
+    }
+}
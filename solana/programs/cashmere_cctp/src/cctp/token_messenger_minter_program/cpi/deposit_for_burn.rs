@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
-/// Account context to invoke [deposit_for_burn_with_caller].
+/// Account context to invoke [deposit_for_burn] and
+/// [deposit_for_burn_with_caller](super::deposit_for_burn_with_caller).
 pub struct DepositForBurn<'info> {
     /// Signer. This account must be the owner of `burn_token`.
     //#[account(signer)]
@@ -55,7 +56,7 @@ pub struct DepositForBurn<'info> {
     pub event_authority: AccountInfo<'info>,
 }
 
-/// Parameters to invoke [deposit_for_burn_with_caller].
+/// Parameters to invoke [deposit_for_burn].
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct DepositForBurnParams {
     /// Transfer (burn) amount.
@@ -72,28 +73,21 @@ pub struct DepositForBurnParams {
     pub mint_recipient: [u8; 32],
 }
 
-// // CPI call to invoke the CCTP Token Messenger Minter program to burn Circle-supported assets.
-// //
-// // NOTE: This instruction requires specifying a specific caller on the destination network. Only
-// // this caller can mint tokens on behalf of the
-// // [mint_recipient](DepositForBurnWithCallerParams::mint_recipient).
-// pub fn deposit_for_burn<'info>(
-//     ctx: CpiContext<'_, '_, '_, 'info, DepositForBurn<'info>>,
-//     args: DepositForBurnParams,
-// ) -> Result<()> {
-//     const ANCHOR_IX_SELECTOR: [u8; 8] = [215, 60, 61, 46, 114, 55, 128, 176];
-//
-//     solana_program::program::invoke_signed(
-//         &solana_program::instruction::Instruction {
-//             program_id: crate::cctp::TOKEN_MESSENGER_MINTER_PROGRAM_ID,
-//             accounts: ctx.to_account_metas(None),
-//             data: (ANCHOR_IX_SELECTOR, args).try_to_vec()?,
-//         },
-//         &ctx.to_account_infos(),
-//         ctx.signer_seeds,
-//     )
-//     .map_err(Into::into)
-// }
+/// CPI call to invoke the CCTP Token Messenger Minter program to burn Circle-supported assets.
+/// Returns the nonce of the CCTP message.
+pub fn deposit_for_burn<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, DepositForBurn<'info>>,
+    args: DepositForBurnParams,
+) -> Result<u64> {
+    const ANCHOR_IX_SELECTOR: [u8; 8] = [215, 60, 61, 46, 114, 55, 128, 176];
+
+    crate::cctp::invoke_anchor_ix_with_return(
+        crate::cctp::TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+        ANCHOR_IX_SELECTOR,
+        ctx,
+        args,
+    )
+}
 
 impl<'info> ToAccountMetas for DepositForBurn<'info> {
     fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use super::DepositForBurn;
+
+/// Parameters to invoke [deposit_for_burn_with_caller].
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct DepositForBurnWithCallerParams {
+    /// Transfer (burn) amount.
+    pub amount: u64,
+
+    /// CCTP domain value of the token to be transferred.
+    pub destination_domain: u32,
+
+    /// Recipient of assets on target network.
+    pub mint_recipient: [u8; 32],
+
+    /// Only this address may receive the message on the destination network.
+    pub destination_caller: [u8; 32],
+}
+
+/// CPI call to invoke the CCTP Token Messenger Minter program to burn Circle-supported assets.
+/// Returns the nonce of the CCTP message.
+///
+/// NOTE: This instruction requires specifying a specific caller on the destination network. Only
+/// this caller can mint tokens on behalf of the
+/// [mint_recipient](DepositForBurnWithCallerParams::mint_recipient).
+pub fn deposit_for_burn_with_caller<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, DepositForBurn<'info>>,
+    args: DepositForBurnWithCallerParams,
+) -> Result<u64> {
+    const ANCHOR_IX_SELECTOR: [u8; 8] = [167, 222, 19, 114, 85, 21, 14, 118];
+
+    crate::cctp::invoke_anchor_ix_with_return(
+        crate::cctp::TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+        ANCHOR_IX_SELECTOR,
+        ctx,
+        args,
+    )
+}
@@ -1,2 +1,8 @@
 mod deposit_for_burn;
 pub use deposit_for_burn::*;
+
+mod deposit_for_burn_with_caller;
+pub use deposit_for_burn_with_caller::*;
+
+mod replace_deposit_for_burn;
+pub use replace_deposit_for_burn::*;
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+/// Account context to invoke [replace_deposit_for_burn].
+pub struct ReplaceDepositForBurn<'info> {
+    /// Signer. Must be the owner of the original burn.
+    //#[account(signer)]
+    pub owner: AccountInfo<'info>,
+
+    //#[account(mut, signer)]
+    pub event_rent_payer: AccountInfo<'info>,
+
+    /// Seeds must be \["sender_authority"\] (CCTP Token Messenger Minter program).
+    pub sender_authority_pda: AccountInfo<'info>,
+
+    /// Mutable. Seeds must be \["message_transmitter"\] (CCTP Message Transmitter program).
+    //#[account(mut)]
+    pub message_transmitter: AccountInfo<'info>,
+
+    /// Seeds must be \["token_messenger"\] (CCTP Token Messenger Minter program).
+    pub token_messenger: AccountInfo<'info>,
+
+    /// New message account holding the replacement message.
+    //#[account(mut, signer)]
+    pub message_sent_event_data: AccountInfo<'info>,
+
+    /// CCTP Message Transmitter program.
+    pub message_transmitter_program: AccountInfo<'info>,
+
+    /// CCTP Token Messenger Minter program.
+    pub token_messenger_minter_program: AccountInfo<'info>,
+
+    pub system_program: AccountInfo<'info>,
+
+    /// Seeds must be \["__event_authority"\] (CCTP Token Messenger Minter program).
+    pub event_authority: AccountInfo<'info>,
+}
+
+/// Parameters to invoke [replace_deposit_for_burn].
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ReplaceDepositForBurnParams {
+    /// Original message bytes, as stored in its message account.
+    pub original_message: Vec<u8>,
+
+    /// Circle attestation of the original message.
+    pub original_attestation: Vec<u8>,
+
+    /// New destination caller. All zeroes means anyone may receive the message.
+    pub new_destination_caller: [u8; 32],
+
+    /// New recipient of assets on target network.
+    pub new_mint_recipient: [u8; 32],
+}
+
+/// CPI call to invoke the CCTP Token Messenger Minter program to replace the recipient and/or
+/// destination caller of an attested burn message. The replacement keeps the original nonce,
+/// which is returned.
+pub fn replace_deposit_for_burn<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, ReplaceDepositForBurn<'info>>,
+    args: ReplaceDepositForBurnParams,
+) -> Result<u64> {
+    const ANCHOR_IX_SELECTOR: [u8; 8] = [7, 27, 93, 132, 1, 80, 19, 163];
+
+    crate::cctp::invoke_anchor_ix_with_return(
+        crate::cctp::TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+        ANCHOR_IX_SELECTOR,
+        ctx,
+        args,
+    )
+}
+
+impl<'info> ToAccountMetas for ReplaceDepositForBurn<'info> {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.owner.key(), true),
+            AccountMeta::new(self.event_rent_payer.key(), true),
+            AccountMeta::new_readonly(self.sender_authority_pda.key(), false),
+            AccountMeta::new(self.message_transmitter.key(), false),
+            AccountMeta::new_readonly(self.token_messenger.key(), false),
+            AccountMeta::new(self.message_sent_event_data.key(), true),
+            AccountMeta::new_readonly(self.message_transmitter_program.key(), false),
+            AccountMeta::new_readonly(self.token_messenger_minter_program.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+            AccountMeta::new_readonly(self.event_authority.key(), false),
+            AccountMeta::new_readonly(self.token_messenger_minter_program.key(), false),
+        ]
+    }
+}
+
+impl<'info> ToAccountInfos<'info> for ReplaceDepositForBurn<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.owner.clone(),
+            self.event_rent_payer.clone(),
+            self.sender_authority_pda.clone(),
+            self.message_transmitter.clone(),
+            self.token_messenger.clone(),
+            self.message_sent_event_data.clone(),
+            self.message_transmitter_program.clone(),
+            self.token_messenger_minter_program.clone(),
+            self.system_program.clone(),
+            self.event_authority.clone(),
+        ]
+    }
+}
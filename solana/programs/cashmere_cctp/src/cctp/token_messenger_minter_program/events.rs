@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DepositForBurn {
+    pub nonce: u64,
+    pub burn_token: Pubkey,
+    pub amount: u64,
+    pub depositor: Pubkey,
+    pub mint_recipient: Pubkey,
+    pub destination_domain: u32,
+    pub destination_token_messenger: Pubkey,
+    pub destination_caller: Pubkey,
+}
+
+crate::cctp::impl_anchor_event!(DepositForBurn, [144, 252, 145, 146, 6, 74, 167, 235]);
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintAndWithdraw {
+    pub mint_recipient: Pubkey,
+    pub amount: u64,
+    pub mint_token: Pubkey,
+}
+
+crate::cctp::impl_anchor_event!(MintAndWithdraw, [75, 67, 229, 70, 162, 126, 0, 71]);
@@ -1,4 +1,5 @@
 pub mod cpi;
+pub mod events;
 
 mod state;
 pub use state::*;
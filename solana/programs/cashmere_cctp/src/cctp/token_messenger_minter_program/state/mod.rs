@@ -1,2 +1,11 @@
 mod local_token;
 pub use local_token::*;
+
+mod remote_token_messenger;
+pub use remote_token_messenger::*;
+
+mod token_messenger;
+pub use token_messenger::*;
+
+mod token_minter;
+pub use token_minter::*;
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenMessenger {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+    pub local_message_transmitter: Pubkey,
+    pub message_body_version: u32,
+    pub authority_bump: u8,
+}
+
+impl TokenMessenger {
+    pub const SEED_PREFIX: &'static [u8] = b"token_messenger";
+}
+
+crate::cctp::impl_anchor_account_readonly!(
+    TokenMessenger,
+    crate::cctp::TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+    [162, 4, 242, 52, 147, 243, 221, 96]
+);
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenMinter {
+    pub token_controller: Pubkey,
+    pub pauser: Pubkey,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl TokenMinter {
+    pub const SEED_PREFIX: &'static [u8] = b"token_minter";
+}
+
+crate::cctp::impl_anchor_account_readonly!(
+    TokenMinter,
+    crate::cctp::TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+    [122, 133, 84, 63, 57, 159, 171, 206]
+);
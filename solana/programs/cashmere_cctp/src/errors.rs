@@ -32,10 +32,175 @@ pub enum TransferError {
     FeeExceedsAmount,
     #[msg("Insufficient SOL amount")]
     NativeAmountTooLow,
+    #[msg("Invalid destination domain")]
+    InvalidDestinationDomain,
+    #[msg("Insufficient USDC balance")]
+    InsufficientUsdcBalance,
+    #[msg("Invalid number of batch legs")]
+    InvalidBatchSize,
+    #[msg("Invalid batch leg accounts")]
+    InvalidBatchAccounts,
+    #[msg("Invalid volume limit tier")]
+    InvalidLimitTier,
+    #[msg("Volume limit exceeded")]
+    VolumeLimitExceeded,
+    #[msg("Destination domain paused by circuit breaker")]
+    DomainPaused,
+    #[msg("Transfer amount below domain minimum")]
+    AmountBelowMinimum,
+    #[msg("Transfer amount above domain maximum")]
+    AmountAboveMaximum,
+    #[msg("Nothing left to burn after fees")]
+    ZeroBurnAmount,
+    #[msg("Burn amount exceeds CCTP burn limit per message")]
+    BurnLimitExceeded,
+    #[msg("Recipient is not a valid address on the destination chain")]
+    InvalidRecipient,
+    #[msg("Solana destinations require a recipient owner")]
+    InvalidSolanaOwner,
+    #[msg("Destination domain has no registered chain family")]
+    UnknownChainFamily,
+    #[msg("Fee or transfer cost overflows")]
+    AmountOverflow,
+}
+
+#[error_code]
+#[derive(Eq, PartialEq)]
+pub enum CctpError {
+    #[msg("Invalid CCTP message transmitter account")]
+    InvalidMessageTransmitter,
+    #[msg("Invalid CCTP token messenger account")]
+    InvalidTokenMessenger,
+    #[msg("CCTP remote token messenger does not match destination domain")]
+    InvalidRemoteTokenMessenger,
+    #[msg("Invalid CCTP token minter account")]
+    InvalidTokenMinter,
+    #[msg("Invalid CCTP sender authority account")]
+    InvalidSenderAuthority,
+    #[msg("Invalid CCTP event authority account")]
+    InvalidEventAuthority,
+    #[msg("CCTP program did not return data")]
+    MissingReturnData,
+}
+
+#[error_code]
+#[derive(Eq, PartialEq)]
+pub enum ReceiptError {
+    #[msg("Signer is not the user of this transfer")]
+    NotTransferUser,
+    #[msg("CCTP message does not belong to this transfer")]
+    MessageMismatch,
+    #[msg("Transfer status can only move forward")]
+    InvalidStatusTransition,
+    #[msg("Transfer is not final")]
+    TransferNotFinal,
+    #[msg("CCTP message account not reclaimed")]
+    MessageNotReclaimed,
+    #[msg("Transfer is no longer awaiting its mint")]
+    TransferNotPending,
 }
 
 #[error_code]
 pub enum ParamError {
     #[msg("Fee basis points too high")]
     FeeTooHigh,
+    #[msg("Volume window must be positive")]
+    InvalidVolumeWindow,
+    #[msg("Config is already at its current size")]
+    ConfigUpToDate,
+    #[msg("Domain out of range")]
+    InvalidDomain,
+    #[msg("Volume limit tier out of range")]
+    InvalidLimitTier,
+    #[msg("Fee tier out of range")]
+    InvalidFeeTier,
+    #[msg("Circuit breaker window must be positive")]
+    InvalidBreakerWindow,
+    #[msg("Minimum transfer amount above maximum")]
+    InvalidTransferAmountRange,
+    #[msg("Gas drop refund timeout must be positive")]
+    InvalidRefundTimeout,
+    #[msg("Gas price max age must not be negative")]
+    InvalidGasPriceMaxAge,
+    #[msg("SOL price max age must be positive")]
+    InvalidSolPriceMaxAge,
+    #[msg("Volume shard out of range")]
+    InvalidShard,
+}
+
+#[error_code]
+#[derive(Eq, PartialEq)]
+pub enum ComplianceError {
+    #[msg("Sender is denylisted")]
+    SenderDenied,
+    #[msg("Recipient is denylisted")]
+    RecipientDenied,
+    #[msg("Invalid denylist entry account")]
+    InvalidDenylistEntry,
+}
+
+#[error_code]
+#[derive(Eq, PartialEq)]
+pub enum IntegratorError {
+    #[msg("Integrator fee above the integrator's maximum")]
+    IntegratorFeeTooHigh,
+    #[msg("Integrator fee requires an integrator")]
+    MissingIntegrator,
+    #[msg("Token account does not belong to the integrator")]
+    InvalidIntegratorTokenAccount,
+}
+
+#[error_code]
+#[derive(Eq, PartialEq)]
+pub enum FeeSplitError {
+    #[msg("Invalid number of fee recipients")]
+    InvalidRecipientCount,
+    #[msg("Fee recipient weights must add up to 10000 bp")]
+    InvalidWeights,
+    #[msg("Fee recipient accounts do not match the fee split")]
+    InvalidRecipientAccounts,
+}
+
+#[error_code]
+#[derive(Eq, PartialEq)]
+pub enum GasDropError {
+    #[msg("Transfer has no gas drop")]
+    NoGasDrop,
+    #[msg("Gas drops require the gas drop escrow of the destination domain")]
+    MissingEscrow,
+    #[msg("Gas drops require a transfer receipt")]
+    MissingReceipt,
+    #[msg("Gas drop escrow balance too low")]
+    InsufficientEscrow,
+    #[msg("Gas drop requires the gas prices of the destination and Solana domains")]
+    MissingGasPrice,
+    #[msg("Gas price is stale")]
+    StaleGasPrice,
+    #[msg("Gas price must be positive")]
+    InvalidGasPrice,
+    #[msg("Gas drop payment outside tolerance of the oracle price")]
+    GasDropPriceMismatch,
+    #[msg("Gas drop refund timeout has not passed")]
+    RefundNotDue,
+    #[msg("Gas drop can only be acknowledged once the transfer is minted")]
+    NotMinted,
+    #[msg("Only the relayer that reported the mint can acknowledge the gas drop")]
+    NotMintingRelayer,
+}
+
+#[error_code]
+#[derive(Eq, PartialEq)]
+pub enum PriceFeedError {
+    #[msg("Native fee requires the SOL/USD price feed")]
+    MissingPriceFeed,
+    #[msg("SOL/USD price update is not fully verified")]
+    UnverifiedPrice,
+    #[msg("SOL/USD price must be positive")]
+    InvalidPrice,
+    #[msg("SOL/USD price is stale")]
+    StalePrice,
+    #[msg("SOL/USD price confidence interval too wide")]
+    PriceUncertain,
+    #[msg("Native fee outside tolerance of its USD value at the oracle price")]
+    NativeFeeMismatch,
 }
@@ -1,8 +1,15 @@
 use anchor_lang::prelude::*;
+use crate::state::{
+    DenylistSubject,
+    TransferStatus,
+    TripReason,
+    VolumeScope,
+};
 
 #[event]
 pub struct TransferEvent {
     pub destination_domain: u32,
+    /// Counts the transfers of `user`; a transfer is identified by `user` and `nonce`.
     pub nonce: u64,
     pub recipient: [u8; 32],
     pub solana_owner: [u8; 32],
@@ -10,6 +17,201 @@ pub struct TransferEvent {
     pub amount: u64,
     pub gas_drop_amount: u64,
     pub fee_is_native: bool,
+    pub source_domain: u32,
     pub cctp_nonce: i64,
     pub cctp_message: Pubkey,
+    /// Total USDC fee, including `integrator_fee`.
+    pub usdc_fee: u64,
+    /// Default if the transfer has no integrator.
+    pub integrator: Pubkey,
+    pub integrator_fee: u64,
+}
+
+/// Full accounting of a `transfer`, emitted alongside [TransferEvent], and likewise for each leg
+/// of a `batch_transfer` and each message of a `transfer_large`. USDC amounts are in micro-USDC
+/// and native amounts in lamports; `gross_amount` = `fee_usdc` + `net_burned`.
+#[event]
+pub struct TransferEventV2 {
+    pub nonce: u64,
+    pub user: Pubkey,
+    pub source_domain: u32,
+    pub destination_domain: u32,
+    pub recipient: [u8; 32],
+    pub solana_owner: [u8; 32],
+    pub burn_token_mint: Pubkey,
+    pub source_token_account: Pubkey,
+    pub gross_amount: u64,
+    /// Total USDC fee, including `integrator_fee`.
+    pub fee_usdc: u64,
+    pub fee_native: u64,
+    /// Percentage fee rate applied, after discounts.
+    pub fee_bp: u64,
+    /// Paid in lamports if `fee_is_native`, otherwise in micro-USDC.
+    pub gas_drop_amount: u64,
+    /// Owed on the destination chain, in the smallest unit of its native token.
+    pub gas_drop_native_amount: u64,
+    pub fee_is_native: bool,
+    pub net_burned: u64,
+    pub integrator: Pubkey,
+    pub integrator_fee: u64,
+    pub config_version: u64,
+    /// Backend quote identifier, covered by the quote signature.
+    pub quote_id: [u8; 16],
+    /// Opaque reference supplied by the client.
+    pub client_memo: Option<[u8; 32]>,
+    pub cctp_nonce: u64,
+    pub cctp_message: Pubkey,
+}
+
+/// Summary of a `batch_transfer`. Each leg also emits a [TransferEvent], with nonces from
+/// `first_nonce` to `last_nonce`.
+#[event]
+pub struct BatchTransferEvent {
+    pub user: Pubkey,
+    pub first_nonce: u64,
+    pub last_nonce: u64,
+    pub amount: u64,
+    pub usdc_fee: u64,
+    pub native_fee: u64,
+    pub fee_is_native: bool,
+}
+
+/// Summary of a `transfer_large`, burned in several CCTP messages. Each message also emits a
+/// [TransferEvent], with nonces from `first_nonce` to `last_nonce`; the fees are charged once and
+/// reported with the first.
+#[event]
+pub struct LargeTransferEvent {
+    pub destination_domain: u32,
+    pub first_nonce: u64,
+    pub last_nonce: u64,
+    pub recipient: [u8; 32],
+    pub solana_owner: [u8; 32],
+    pub user: Pubkey,
+    pub amount: u64,
+    pub fee_is_native: bool,
+    pub source_domain: u32,
+    pub cctp_nonces: Vec<u64>,
+    pub cctp_messages: Vec<Pubkey>,
+}
+
+#[event]
+pub struct TransferReplaced {
+    pub nonce: u64,
+    pub user: Pubkey,
+    pub cctp_nonce: u64,
+    pub new_recipient: [u8; 32],
+    pub new_solana_owner: [u8; 32],
+    pub new_destination_caller: [u8; 32],
+    pub cctp_message: Pubkey,
+}
+
+#[event]
+pub struct EventAccountReclaimed {
+    pub nonce: u64,
+    pub cctp_message: Pubkey,
+    pub rent_payer: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct GasPriceUpdated {
+    pub domain: u32,
+    pub price: u64,
+    pub decimals: u8,
+}
+
+#[event]
+pub struct GasDropAcknowledged {
+    pub nonce: u64,
+    pub relayer: Pubkey,
+    pub destination_tx_hash: [u8; 32],
+}
+
+#[event]
+pub struct GasDropRefunded {
+    pub nonce: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub fee_is_native: bool,
+}
+
+#[event]
+pub struct TransferStatusUpdated {
+    pub nonce: u64,
+    pub status: TransferStatus,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct RelayerAdded {
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct RelayerRemoved {
+    pub authority: Pubkey,
+}
+
+/// A transfer brought a rolling volume window up to its limit. Further transfers in that scope
+/// fail with `VolumeLimitExceeded` until the window rolls. Global and domain volumes and limits
+/// are those of the user's volume shard.
+#[event]
+pub struct VolumeLimitReached {
+    pub scope: VolumeScope,
+    pub destination_domain: u32,
+    pub user: Pubkey,
+    pub volume: u64,
+    pub limit: u64,
+}
+
+/// The circuit breaker of `destination_domain` in volume shard `shard` tripped; transfers to it
+/// by users of the shard fail until the guardian or owner resets it.
+#[event]
+pub struct CircuitBreakerTripped {
+    pub destination_domain: u32,
+    pub shard: u8,
+    pub reason: TripReason,
+    pub outflow: u64,
+    pub slot_transfers: u32,
+    pub slot: u64,
+}
+
+#[event]
+pub struct CircuitBreakerReset {
+    pub destination_domain: u32,
+    pub shard: u8,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct DenylistEntryAdded {
+    pub subject: DenylistSubject,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct DenylistEntryRemoved {
+    pub subject: DenylistSubject,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct IntegratorUpdated {
+    pub authority: Pubkey,
+    pub fee_token_account: Pubkey,
+    pub max_fee_bp: u64,
+}
+
+#[event]
+pub struct IntegratorRemoved {
+    pub authority: Pubkey,
+}
+
+/// One recipient's share of a `distribute_fees` payout.
+#[event]
+pub struct FeePayout {
+    pub wallet: Pubkey,
+    pub token_account: Pubkey,
+    pub usdc_amount: u64,
+    pub native_amount: u64,
 }
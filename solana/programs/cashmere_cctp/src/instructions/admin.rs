@@ -1,17 +1,31 @@
 use anchor_lang::prelude::*;
-use crate::state::Config;
+use crate::state::{
+    ChainFamily,
+    CircuitBreaker,
+    Config,
+    FeeTier,
+    FEE_TIERS,
+    MAX_DOMAINS,
+    VOLUME_LIMIT_TIERS,
+    VOLUME_SHARDS,
+    VolumeShard,
+};
 use crate::errors::ParamError;
+use crate::events::CircuitBreakerReset;
+use crate::utils::emit_event;
 
 pub fn set_fee_bp_ix(ctx: Context<ConfigContext>, fee_bp: u64) -> Result<()> {
     assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set fee parameters");
     require!(fee_bp <= 10000, ParamError::FeeTooHigh);
     ctx.accounts.config.fee_bp = fee_bp;
+    ctx.accounts.config.version += 1;
     Ok(())
 }
 
 pub fn set_signer_key_ix(ctx: Context<ConfigContext>, signer_key: [u8; 32]) -> Result<()> {
     assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set signer key");
     ctx.accounts.config.signer_key = signer_key;
+    ctx.accounts.config.version += 1;
     Ok(())
 }
 
@@ -19,6 +33,7 @@ pub fn set_fee_collector_ix(ctx: Context<ConfigContext>, fee_collector_sol: Pubk
     assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set fee parameters");
     ctx.accounts.config.fee_collector_sol = fee_collector_sol;
     ctx.accounts.config.fee_collector_usdc = fee_collector_usdc;
+    ctx.accounts.config.version += 1;
     Ok(())
 }
 
@@ -26,31 +41,208 @@ pub fn set_gas_drop_collector_ix(ctx: Context<ConfigContext>, gas_drop_collector
     assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set fee parameters");
     ctx.accounts.config.gas_drop_collector_sol = gas_drop_collector_sol;
     ctx.accounts.config.gas_drop_collector_usdc = gas_drop_collector_usdc;
+    ctx.accounts.config.version += 1;
     Ok(())
 }
 
 pub fn set_max_usdc_gas_drop_ix(ctx: Context<ConfigContext>, max_gas: u64) -> Result<()> {
     assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
     ctx.accounts.config.max_usdc_gas_drop = max_gas;
+    ctx.accounts.config.version += 1;
     Ok(())
 }
 
 pub fn set_max_native_gas_drop_ix(ctx: Context<ConfigContext>, destination_domain: u32, max_gas: u64) -> Result<()> {
     assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!((destination_domain as usize) < MAX_DOMAINS, ParamError::InvalidDomain);
     ctx.accounts.config.max_native_gas_drop[destination_domain as usize] = max_gas;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+/// Sets the accepted `usdc_amount` range for `destination_domain`. A zero `max_amount` means
+/// unlimited.
+pub fn set_transfer_amount_limits_ix(
+    ctx: Context<ConfigContext>,
+    destination_domain: u32,
+    min_amount: u64,
+    max_amount: u64,
+) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!((destination_domain as usize) < MAX_DOMAINS, ParamError::InvalidDomain);
+    require!(max_amount == 0 || min_amount <= max_amount, ParamError::InvalidTransferAmountRange);
+    ctx.accounts.config.min_transfer_amount[destination_domain as usize] = min_amount;
+    ctx.accounts.config.max_transfer_amount[destination_domain as usize] = max_amount;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn set_chain_family_ix(ctx: Context<ConfigContext>, destination_domain: u32, chain_family: ChainFamily) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!((destination_domain as usize) < MAX_DOMAINS, ParamError::InvalidDomain);
+    ctx.accounts.config.chain_family[destination_domain as usize] = chain_family;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+/// Sets fee tier `index`. A zero `min_volume` disables it.
+pub fn set_fee_tier_ix(ctx: Context<ConfigContext>, index: u8, min_volume: u64, discount_bp: u64) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set fee parameters");
+    require!((index as usize) < FEE_TIERS, ParamError::InvalidFeeTier);
+    require!(discount_bp <= 10000, ParamError::FeeTooHigh);
+    ctx.accounts.config.fee_tiers[index as usize] = FeeTier {
+        min_volume,
+        discount_bp,
+    };
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn set_volume_window_ix(ctx: Context<ConfigContext>, volume_window: i64) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!(volume_window > 0, ParamError::InvalidVolumeWindow);
+    ctx.accounts.config.volume_window = volume_window;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn set_global_volume_limit_ix(ctx: Context<ConfigContext>, limit: u64) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    ctx.accounts.config.global_volume_limit = limit;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn set_domain_volume_limit_ix(ctx: Context<ConfigContext>, destination_domain: u32, limit: u64) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!((destination_domain as usize) < MAX_DOMAINS, ParamError::InvalidDomain);
+    ctx.accounts.config.domain_volume_limit[destination_domain as usize] = limit;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn set_user_volume_limit_ix(ctx: Context<ConfigContext>, limit_tier: u8, limit: u64) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!((limit_tier as usize) < VOLUME_LIMIT_TIERS, ParamError::InvalidLimitTier);
+    ctx.accounts.config.user_volume_limit[limit_tier as usize] = limit;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn set_guardian_ix(ctx: Context<ConfigContext>, guardian: Pubkey) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set guardian");
+    ctx.accounts.config.guardian = guardian;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn set_circuit_breaker_ix(
+    ctx: Context<ConfigContext>,
+    breaker_window: i64,
+    max_transfers_per_slot: u32,
+) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!(breaker_window > 0, ParamError::InvalidBreakerWindow);
+    ctx.accounts.config.breaker_window = breaker_window;
+    ctx.accounts.config.breaker_max_transfers_per_slot = max_transfers_per_slot;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn set_breaker_outflow_limit_ix(ctx: Context<ConfigContext>, destination_domain: u32, limit: u64) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!((destination_domain as usize) < MAX_DOMAINS, ParamError::InvalidDomain);
+    ctx.accounts.config.breaker_outflow_limit[destination_domain as usize] = limit;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+/// Sets how long a gas drop goes unacknowledged before its user can claim a refund, in seconds.
+pub fn set_gas_drop_refund_timeout_ix(ctx: Context<ConfigContext>, timeout: i64) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!(timeout > 0, ParamError::InvalidRefundTimeout);
+    ctx.accounts.config.gas_drop_refund_timeout = timeout;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn set_gas_oracle_ix(ctx: Context<ConfigContext>, gas_oracle: Pubkey) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    ctx.accounts.config.gas_oracle = gas_oracle;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+/// Sets how closely gas drop payments must match the oracle price. A zero `max_age` turns the
+/// check off.
+pub fn set_gas_price_check_ix(ctx: Context<ConfigContext>, tolerance_bp: u64, max_age: i64) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!(tolerance_bp <= 10000, ParamError::FeeTooHigh);
+    require!(max_age >= 0, ParamError::InvalidGasPriceMaxAge);
+    ctx.accounts.config.gas_price_tolerance_bp = tolerance_bp;
+    ctx.accounts.config.gas_price_max_age = max_age;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+/// Sets the Pyth SOL/USD price account native fees are checked against, and how strictly. The
+/// default pubkey turns the check off.
+pub fn set_sol_price_feed_ix(
+    ctx: Context<ConfigContext>,
+    sol_price_feed: Pubkey,
+    tolerance_bp: u64,
+    max_age: i64,
+    max_conf_bp: u64,
+) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!(tolerance_bp <= 10000 && max_conf_bp <= 10000, ParamError::FeeTooHigh);
+    require!(max_age > 0, ParamError::InvalidSolPriceMaxAge);
+    ctx.accounts.config.sol_price_feed = sol_price_feed;
+    ctx.accounts.config.native_fee_tolerance_bp = tolerance_bp;
+    ctx.accounts.config.sol_price_max_age = max_age;
+    ctx.accounts.config.sol_price_max_conf_bp = max_conf_bp;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+/// Creates volume shard `shard`. Transfers fail for the users of a shard until it exists.
+pub fn init_volume_shard_ix(ctx: Context<InitVolumeShardContext>, shard: u8) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    require!((shard as usize) < VOLUME_SHARDS, ParamError::InvalidShard);
+    let volume_shard = &mut ctx.accounts.volume_shard;
+    volume_shard.bump = ctx.bumps.volume_shard;
+    volume_shard.shard = shard;
+    Ok(())
+}
+
+/// Unpauses `destination_domain` for the users of `shard` and clears its breaker counters.
+pub fn reset_circuit_breaker_ix(ctx: Context<GuardianContext>, destination_domain: u32, shard: u8) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    assert!(
+        authority == ctx.accounts.config.guardian || authority == ctx.accounts.config.owner,
+        "Only the guardian or owner can reset the circuit breaker"
+    );
+    require!((destination_domain as usize) < MAX_DOMAINS, ParamError::InvalidDomain);
+    ctx.accounts.volume_shard.breakers[destination_domain as usize] = CircuitBreaker::default();
+    emit_event!(ctx, CircuitBreakerReset {
+        destination_domain,
+        shard,
+        authority,
+    });
     Ok(())
 }
 
 pub fn transfer_ownership_ix(ctx: Context<TransferOwnershipContext>, new_owner: Pubkey) -> Result<()> {
     assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the owner can transfer ownership");
     ctx.accounts.config.owner = new_owner;
+    ctx.accounts.config.version += 1;
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct ConfigContext<'info> {
     #[account(mut, seeds=[b"config"], bump)]
-    pub config: Account<'info, Config>,
+    pub config: Box<Account<'info, Config>>,
 
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -58,10 +250,50 @@ pub struct ConfigContext<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct InitVolumeShardContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// Seeds must be \["volume_shard", shard\].
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + VolumeShard::INIT_SPACE,
+        seeds = [VolumeShard::SEED_PREFIX, &[shard]],
+        bump,
+    )]
+    pub volume_shard: Box<Account<'info, VolumeShard>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(destination_domain: u32, shard: u8)]
+pub struct GuardianContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// Seeds must be \["volume_shard", shard\].
+    #[account(
+        mut,
+        seeds = [VolumeShard::SEED_PREFIX, &[shard]],
+        bump = volume_shard.bump,
+    )]
+    pub volume_shard: Box<Account<'info, VolumeShard>>,
+
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct TransferOwnershipContext<'info> {
     #[account(mut, seeds=[b"config"], bump)]
-    pub config: Account<'info, Config>,
+    pub config: Box<Account<'info, Config>>,
 
     #[account(mut)]
     pub owner: Signer<'info>,
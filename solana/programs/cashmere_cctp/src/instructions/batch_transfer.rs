@@ -0,0 +1,471 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as sysvar};
+use anchor_spl::token::{
+    self,
+    Token,
+    TokenAccount,
+    Transfer as SplTransfer
+};
+use borsh::{BorshSerialize, to_vec};
+use crate::{
+    instructions::transfer::{
+        check_balances,
+        check_burn_limit,
+        check_deadline,
+        track_outflow,
+        track_volume,
+        BurnAccounts,
+        FeeAccounts,
+        CUSTODY_SEED_PREFIX,
+    },
+    utils::{
+        emit_event,
+        verify_ed25519_ix,
+        check_unconstrained_recipient_allowed,
+        check_sender_allowed,
+        check_native_fee,
+        effective_fee_bp,
+        quote_transfer,
+        validate_recipient,
+    },
+    state::{
+        Custodian,
+        Config,
+        DenylistEntry,
+        FeeDiscount,
+        TransferQuote,
+        UserState,
+        VolumeShard,
+    },
+    pyth::PriceUpdateV2,
+    events::{
+        BatchTransferEvent,
+        TransferEvent,
+        TransferEventV2,
+    },
+    cctp::{
+        pda,
+        LOCAL_DOMAIN,
+        TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+        MESSAGE_TRANSMITTER_PROGRAM_ID,
+        token_messenger_minter_program::{
+            LocalToken,
+            cpi::DepositForBurnParams,
+        },
+    },
+    errors::{
+        TransferError,
+        CctpError,
+    },
+};
+
+pub const MAX_BATCH_LEGS: usize = 8;
+
+/// Accounts each leg expects in remaining accounts, in this order.
+const ACCOUNTS_PER_LEG: usize = 3;
+
+/// One destination of a batch transfer.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TransferLeg {
+    pub usdc_amount: u64,
+    pub destination_domain: u32,
+    pub recipient: [u8; 32],
+    pub solana_owner: [u8; 32],
+}
+
+#[derive(BorshSerialize)]
+struct BatchTransferParams<'a> {
+    local_domain: u32,
+    legs: &'a [TransferLeg],
+    fee: u64,
+    fee_usd: u64,
+    deadline: u64,
+    fee_is_native: bool,
+    limit_tier: u8,
+    quote_id: [u8; 16],
+}
+
+/// Fans a USDC transfer out to several destinations under one signed quote. `fee` is the static
+/// fee for the whole batch and is charged once, on the first leg, with `fee_usd` its micro-USD
+/// value if paid in SOL; the percentage fee applies per leg. Legs carry no gas drop, as they
+/// get no receipt to escrow it against.
+///
+/// The quote signature covers every leg in full. Remaining accounts hold, for each leg in order,
+/// its remote token messenger, a fresh signer for its CCTP message account and the denylist PDA
+/// of its recipient. The user pays message rent directly and can reclaim it from CCTP.
+#[allow(clippy::too_many_arguments)]
+pub fn batch_transfer_ix<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchTransferContext<'info>>,
+    legs: Vec<TransferLeg>,
+    fee: u64,
+    fee_usd: u64,
+    deadline: u64,
+    fee_is_native: bool,
+    limit_tier: u8,
+    quote_id: [u8; 16],
+) -> Result<()> {
+    if ctx.accounts.token_program.key() != token::ID {
+        return Err(TransferError::InvalidTokenProgram.into());
+    }
+    require!(!legs.is_empty() && legs.len() <= MAX_BATCH_LEGS, TransferError::InvalidBatchSize);
+    require!(ctx.remaining_accounts.len() == legs.len() * ACCOUNTS_PER_LEG, TransferError::InvalidBatchAccounts);
+
+    let msg = BatchTransferParams {
+        local_domain: LOCAL_DOMAIN,
+        legs: &legs,
+        fee,
+        fee_usd,
+        deadline,
+        fee_is_native,
+        limit_tier,
+        quote_id,
+    };
+    verify_ed25519_ix(&ctx.accounts.signature, &to_vec(&msg)?, &ctx.accounts.config.signer_key)?;
+    check_deadline(deadline)?;
+    check_sender_allowed(&ctx.accounts.sender_denylist_entry)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    check_native_fee(
+        &ctx.accounts.config,
+        ctx.accounts.sol_price_update.as_deref().map(|price_update| &**price_update),
+        fee,
+        fee_usd,
+        fee_is_native,
+        now,
+    )?;
+
+    let fee_bp = effective_fee_bp(
+        &ctx.accounts.config,
+        ctx.accounts.fee_discount.as_deref(),
+        ctx.accounts.user_state.cumulative_volume,
+        now,
+    );
+    let mut quotes = Vec::with_capacity(legs.len());
+    for (i, (leg, leg_accounts)) in legs.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_LEG)).enumerate() {
+        let quote = quote_transfer(
+            &ctx.accounts.config,
+            fee_bp,
+            leg.usdc_amount,
+            leg.destination_domain,
+            if i == 0 { fee } else { 0 },
+            0,
+            fee_is_native,
+            0,
+        );
+        quote.validate()?;
+        validate_recipient(&ctx.accounts.config, leg.destination_domain, &leg.recipient, &leg.solana_owner, ctx.accounts.burn_token_mint.key)?;
+        check_unconstrained_recipient_allowed(&leg_accounts[2], leg.destination_domain, &leg.recipient)?;
+        check_burn_limit(&ctx.accounts.local_token, quote.net_burn_amount)?;
+        for event in track_volume(
+            &ctx.accounts.config,
+            &mut ctx.accounts.volume_shard,
+            &mut ctx.accounts.user_state,
+            ctx.accounts.owner.key(),
+            ctx.bumps.user_state,
+            leg.destination_domain,
+            limit_tier,
+            quote.net_burn_amount,
+        )? {
+            emit_event!(ctx, event);
+        }
+        if let Some(event) = track_outflow(&ctx.accounts.config, &mut ctx.accounts.volume_shard, leg.destination_domain, quote.net_burn_amount)? {
+            emit_event!(ctx, event);
+        }
+        quotes.push(quote);
+    }
+    let total = |f: fn(&TransferQuote) -> u64| quotes.iter().map(f).sum::<u64>();
+    let usdc_fee = total(|q| q.usdc_fee);
+    let amount = total(|q| q.net_burn_amount);
+    check_balances(&ctx.accounts.owner_token_account, &ctx.accounts.owner, total(|q| q.total_usdc_cost), total(|q| q.total_native_cost))?;
+
+    ctx.accounts.fee_accounts().collect(usdc_fee, if fee_is_native { fee } else { 0 }, 0, fee_is_native)?;
+
+    let custodian_seeds: &[&[u8]] = &[Custodian::SEED_PREFIX, &[ctx.accounts.custodian.bump]];
+
+    token::transfer(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        SplTransfer {
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            to: ctx.accounts.burn_token_account.to_account_info(),
+            authority: ctx.accounts.custodian.to_account_info(),
+        },
+        &[custodian_seeds],
+    ), amount)?;
+
+    let burn_accounts = ctx.accounts.burn_accounts();
+    let first_nonce = ctx.accounts.user_state.nonce + 1;
+    for ((leg, quote), leg_accounts) in legs.iter().zip(&quotes).zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_LEG)) {
+        let remote_token_messenger = &leg_accounts[0];
+        let message_sent_event_data = &leg_accounts[1];
+        require_keys_eq!(
+            remote_token_messenger.key(),
+            pda::remote_token_messenger(leg.destination_domain),
+            CctpError::InvalidRemoteTokenMessenger
+        );
+        require!(message_sent_event_data.is_signer, TransferError::InvalidBatchAccounts);
+
+        let cctp_nonce = burn_accounts.deposit_for_burn(
+            ctx.accounts.owner.to_account_info(),
+            remote_token_messenger.clone(),
+            message_sent_event_data.clone(),
+            DepositForBurnParams {
+                amount: quote.net_burn_amount,
+                destination_domain: leg.destination_domain,
+                mint_recipient: leg.recipient,
+            },
+            &[custodian_seeds],
+        )?;
+
+        ctx.accounts.user_state.nonce += 1;
+        let nonce = ctx.accounts.user_state.nonce;
+
+        emit_event!(ctx, TransferEvent {
+            destination_domain: leg.destination_domain,
+            nonce,
+            recipient: leg.recipient,
+            solana_owner: leg.solana_owner,
+            user: ctx.accounts.owner.key(),
+            amount: quote.net_burn_amount,
+            gas_drop_amount: 0,
+            source_domain: LOCAL_DOMAIN,
+            cctp_nonce: cctp_nonce as i64,
+            fee_is_native,
+            cctp_message: message_sent_event_data.key(),
+            usdc_fee: quote.usdc_fee,
+            integrator: Pubkey::default(),
+            integrator_fee: 0,
+        });
+
+        emit_event!(ctx, TransferEventV2 {
+            nonce,
+            user: ctx.accounts.owner.key(),
+            source_domain: LOCAL_DOMAIN,
+            destination_domain: leg.destination_domain,
+            recipient: leg.recipient,
+            solana_owner: leg.solana_owner,
+            burn_token_mint: ctx.accounts.burn_token_mint.key(),
+            source_token_account: ctx.accounts.owner_token_account.key(),
+            gross_amount: leg.usdc_amount,
+            fee_usdc: quote.usdc_fee,
+            fee_native: quote.native_fee,
+            fee_bp: quote.fee_bp,
+            gas_drop_amount: 0,
+            gas_drop_native_amount: 0,
+            fee_is_native,
+            net_burned: quote.net_burn_amount,
+            integrator: Pubkey::default(),
+            integrator_fee: 0,
+            config_version: ctx.accounts.config.version,
+            quote_id,
+            client_memo: None,
+            cctp_nonce,
+            cctp_message: message_sent_event_data.key(),
+        });
+    }
+
+    emit_event!(ctx, BatchTransferEvent {
+        user: ctx.accounts.owner.key(),
+        first_nonce,
+        last_nonce: ctx.accounts.user_state.nonce,
+        amount,
+        usdc_fee,
+        native_fee: if fee_is_native { fee } else { 0 },
+        fee_is_native,
+    });
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.burn_token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.custodian.to_account_info(),
+        },
+        &[custodian_seeds],
+    ))
+}
+
+impl<'info> BatchTransferContext<'info> {
+    pub(crate) fn fee_accounts(&self) -> FeeAccounts<'info> {
+        FeeAccounts {
+            owner: self.owner.to_account_info(),
+            owner_token_account: self.owner_token_account.to_account_info(),
+            fee_collector_sol: self.fee_collector_sol_account.to_account_info(),
+            fee_collector_usdc: self.fee_collector_usdc_account.to_account_info(),
+            gas_drop_collector_sol: self.gas_drop_collector_sol_account.to_account_info(),
+            gas_drop_collector_usdc: self.gas_drop_collector_usdc_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+
+    pub(crate) fn burn_accounts(&self) -> BurnAccounts<'info> {
+        BurnAccounts {
+            custodian: self.custodian.to_account_info(),
+            burn_token_account: self.burn_token_account.to_account_info(),
+            burn_token_mint: self.burn_token_mint.to_account_info(),
+            local_token: self.local_token.to_account_info(),
+            sender_authority: self.token_messenger_minter_sender_authority.to_account_info(),
+            message_transmitter: self.message_transmitter.to_account_info(),
+            token_messenger: self.token_messenger.to_account_info(),
+            token_minter: self.token_minter.to_account_info(),
+            event_authority: self.token_messenger_minter_event_authority.to_account_info(),
+            message_transmitter_program: self.message_transmitter_program.to_account_info(),
+            token_messenger_minter_program: self.token_messenger_minter_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+}
+
+/// Same accounts as [TransferContext](super::TransferContext), minus the per-destination ones
+/// which are passed per leg in remaining accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BatchTransferContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = config.fee_collector_sol,
+    )]
+    pub fee_collector_sol_account: SystemAccount<'info>,
+    #[account(
+        mut,
+        address = config.fee_collector_usdc,
+    )]
+    pub fee_collector_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = config.gas_drop_collector_sol,
+    )]
+    pub gas_drop_collector_sol_account: SystemAccount<'info>,
+    #[account(
+        mut,
+        address = config.gas_drop_collector_usdc,
+    )]
+    pub gas_drop_collector_usdc_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Rolling transfer volume and nonce of `owner`. Seeds must be \["user", owner\].
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + UserState::INIT_SPACE,
+        seeds = [UserState::SEED_PREFIX, owner.key().as_ref()],
+        bump,
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    /// Global and domain volume windows and circuit breakers `owner` is tracked in. Seeds must be
+    /// \["volume_shard", VolumeShard::index(owner)\].
+    #[account(
+        mut,
+        seeds = [VolumeShard::SEED_PREFIX, &[VolumeShard::index(&owner.key())]],
+        bump = volume_shard.bump,
+    )]
+    pub volume_shard: Box<Account<'info, VolumeShard>>,
+
+    /// Negotiated rate of `owner`, if any. Seeds must be \["fee_discount", owner\].
+    #[account(
+        seeds = [FeeDiscount::SEED_PREFIX, owner.key().as_ref()],
+        bump = fee_discount.bump,
+    )]
+    pub fee_discount: Option<Account<'info, FeeDiscount>>,
+
+    /// CHECK: Must not exist. Seeds must be \["denied_sender", owner\].
+    #[account(
+        seeds = [DenylistEntry::SENDER_SEED_PREFIX, owner.key().as_ref()],
+        bump,
+    )]
+    pub sender_denylist_entry: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+
+    // cctp
+
+    /// This program's emitter authority.
+    ///
+    /// Seeds must be \["emitter"\].
+    #[account(
+        seeds = [Custodian::SEED_PREFIX],
+        bump = custodian.bump,
+    )]
+    pub(crate) custodian: Account<'info, Custodian>,
+
+    /// Circle-supported mint.
+    ///
+    /// CHECK: Mutable. This token account's mint must be the same as the one found in the CCTP
+    /// Token Messenger Minter program's local token account.
+    #[account(
+        mut,
+        address = local_token.mint,
+    )]
+    pub(crate) burn_token_mint: AccountInfo<'info>,
+
+    /// Temporary custody token account holding the net amount of every leg. This account will be
+    /// closed at the end of this instruction.
+    ///
+    /// Seeds must be \["__custody", owner, nonce\], where nonce is `user_state.nonce + 1`.
+    #[account(
+        init,
+        payer = owner,
+        token::mint = burn_token_mint,
+        token::authority = custodian,
+        seeds = [CUSTODY_SEED_PREFIX, owner.key().as_ref(), &(user_state.nonce + 1).to_le_bytes()],
+        bump,
+    )]
+    pub(crate) burn_token_account: Box<Account<'info, token::TokenAccount>>,
+
+    /// Local token account, which this program uses to validate the `mint` used to burn.
+    ///
+    /// Mutable. Seeds must be \["local_token", mint\] (CCTP Token Messenger Minter program).
+    #[account(mut)]
+    pub(crate) local_token: Box<Account<'info, LocalToken>>,
+
+    /// CHECK: Must equal CCTP Token Messenger Minter program ID.
+    #[account(address = TOKEN_MESSENGER_MINTER_PROGRAM_ID)]
+    token_messenger_minter_program: UncheckedAccount<'info>,
+
+    /// CHECK: Must equal CCTP Message Transmitter program ID.
+    #[account(address = MESSAGE_TRANSMITTER_PROGRAM_ID)]
+    message_transmitter_program: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds must be \["__event_authority"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MESSENGER_MINTER_EVENT_AUTHORITY @ CctpError::InvalidEventAuthority)]
+    token_messenger_minter_event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mutable. Seeds must be \["message_transmitter"\] (CCTP Message Transmitter program).
+    #[account(
+        mut,
+        address = pda::MESSAGE_TRANSMITTER @ CctpError::InvalidMessageTransmitter,
+    )]
+    message_transmitter: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds must be \["token_messenger"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MESSENGER @ CctpError::InvalidTokenMessenger)]
+    token_messenger: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds must be \["token_minter"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MINTER @ CctpError::InvalidTokenMinter)]
+    token_minter: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds must be \["sender_authority"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MESSENGER_MINTER_SENDER_AUTHORITY @ CctpError::InvalidSenderAuthority)]
+    token_messenger_minter_sender_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Safe because it's a sysvar account
+    #[account(address = sysvar::ID)]
+    pub signature: AccountInfo<'info>,
+
+    /// Pyth SOL/USD price update at `config.sol_price_feed`. Required for native fees while the
+    /// feed is set.
+    #[account(address = config.sol_price_feed)]
+    pub sol_price_update: Option<Box<Account<'info, PriceUpdateV2>>>,
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        Config,
+        DenylistEntry,
+        DenylistSubject,
+    },
+    events::{
+        DenylistEntryAdded,
+        DenylistEntryRemoved,
+    },
+    utils::emit_event,
+};
+
+pub fn set_compliance_ix(ctx: Context<SetComplianceContext>, compliance: Pubkey) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the owner can set compliance");
+    ctx.accounts.config.compliance = compliance;
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+pub fn deny_sender_ix(ctx: Context<DenySenderContext>, sender: Pubkey) -> Result<()> {
+    ctx.accounts.entry.set_inner(DenylistEntry {
+        bump: ctx.bumps.entry,
+        added_at: Clock::get()?.unix_timestamp,
+    });
+    emit_event!(ctx, DenylistEntryAdded {
+        subject: DenylistSubject::Sender { sender },
+        authority: ctx.accounts.compliance.key(),
+    });
+    Ok(())
+}
+
+pub fn allow_sender_ix(ctx: Context<AllowSenderContext>, sender: Pubkey) -> Result<()> {
+    emit_event!(ctx, DenylistEntryRemoved {
+        subject: DenylistSubject::Sender { sender },
+        authority: ctx.accounts.compliance.key(),
+    });
+    Ok(())
+}
+
+pub fn deny_recipient_ix(ctx: Context<DenyRecipientContext>, destination_domain: u32, recipient: [u8; 32]) -> Result<()> {
+    ctx.accounts.entry.set_inner(DenylistEntry {
+        bump: ctx.bumps.entry,
+        added_at: Clock::get()?.unix_timestamp,
+    });
+    emit_event!(ctx, DenylistEntryAdded {
+        subject: DenylistSubject::Recipient { destination_domain, recipient },
+        authority: ctx.accounts.compliance.key(),
+    });
+    Ok(())
+}
+
+pub fn allow_recipient_ix(ctx: Context<AllowRecipientContext>, destination_domain: u32, recipient: [u8; 32]) -> Result<()> {
+    emit_event!(ctx, DenylistEntryRemoved {
+        subject: DenylistSubject::Recipient { destination_domain, recipient },
+        authority: ctx.accounts.compliance.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetComplianceContext<'info> {
+    #[account(mut, seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sender: Pubkey)]
+pub struct DenySenderContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = compliance,
+        space = 8 + DenylistEntry::INIT_SPACE,
+        seeds = [DenylistEntry::SENDER_SEED_PREFIX, sender.as_ref()],
+        bump,
+    )]
+    pub entry: Account<'info, DenylistEntry>,
+
+    #[account(mut, address = config.compliance)]
+    pub compliance: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sender: Pubkey)]
+pub struct AllowSenderContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        close = compliance,
+        seeds = [DenylistEntry::SENDER_SEED_PREFIX, sender.as_ref()],
+        bump = entry.bump,
+    )]
+    pub entry: Account<'info, DenylistEntry>,
+
+    #[account(mut, address = config.compliance)]
+    pub compliance: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(destination_domain: u32, recipient: [u8; 32])]
+pub struct DenyRecipientContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = compliance,
+        space = 8 + DenylistEntry::INIT_SPACE,
+        seeds = [DenylistEntry::RECIPIENT_SEED_PREFIX, &destination_domain.to_le_bytes(), recipient.as_ref()],
+        bump,
+    )]
+    pub entry: Account<'info, DenylistEntry>,
+
+    #[account(mut, address = config.compliance)]
+    pub compliance: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(destination_domain: u32, recipient: [u8; 32])]
+pub struct AllowRecipientContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        close = compliance,
+        seeds = [DenylistEntry::RECIPIENT_SEED_PREFIX, &destination_domain.to_le_bytes(), recipient.as_ref()],
+        bump = entry.bump,
+    )]
+    pub entry: Account<'info, DenylistEntry>,
+
+    #[account(mut, address = config.compliance)]
+    pub compliance: Signer<'info>,
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        Config,
+        Discount,
+        FeeDiscount,
+    },
+    errors::ParamError,
+};
+
+pub fn set_fee_discount_ix(ctx: Context<SetFeeDiscountContext>, user: Pubkey, discount: Discount, expires_at: i64) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set fee parameters");
+    let (Discount::FeeBp { fee_bp: bp } | Discount::Percentage { discount_bp: bp }) = discount;
+    require!(bp <= 10000, ParamError::FeeTooHigh);
+    ctx.accounts.fee_discount.set_inner(FeeDiscount {
+        bump: ctx.bumps.fee_discount,
+        user,
+        discount,
+        expires_at,
+    });
+    Ok(())
+}
+
+pub fn remove_fee_discount_ix(ctx: Context<RemoveFeeDiscountContext>, _user: Pubkey) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can remove fee discounts");
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct SetFeeDiscountContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + FeeDiscount::INIT_SPACE,
+        seeds = [FeeDiscount::SEED_PREFIX, user.as_ref()],
+        bump,
+    )]
+    pub fee_discount: Account<'info, FeeDiscount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RemoveFeeDiscountContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [FeeDiscount::SEED_PREFIX, user.as_ref()],
+        bump = fee_discount.bump,
+    )]
+    pub fee_discount: Account<'info, FeeDiscount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
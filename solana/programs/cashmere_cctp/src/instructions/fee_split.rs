@@ -0,0 +1,191 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::{
+    self,
+    Mint,
+    Token,
+    TokenAccount,
+    Transfer as SplTransfer
+};
+use crate::{
+    state::{
+        Config,
+        FeeRecipient,
+        FeeSplit,
+        MAX_FEE_RECIPIENTS,
+    },
+    events::FeePayout,
+    errors::FeeSplitError,
+    utils::emit_event,
+};
+
+/// Accounts each recipient expects in the remaining accounts of `distribute_fees`, in this order.
+const ACCOUNTS_PER_RECIPIENT: usize = 2;
+
+/// Sets how accrued fees are split, and points the config fee collectors at the fee vaults. The
+/// SOL vault is funded with its rent-exempt minimum on creation, so it can take fees of any size.
+pub fn set_fee_split_ix(ctx: Context<SetFeeSplitContext>, recipients: Vec<FeeRecipient>) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set fee parameters");
+    require!(
+        !recipients.is_empty() && recipients.len() <= MAX_FEE_RECIPIENTS,
+        FeeSplitError::InvalidRecipientCount
+    );
+    require!(
+        recipients.iter().map(|recipient| recipient.weight_bp as u64).sum::<u64>() == 10000,
+        FeeSplitError::InvalidWeights
+    );
+
+    let rent_exempt = Rent::get()?.minimum_balance(0);
+    let vault_lamports = ctx.accounts.fee_vault.lamports();
+    if vault_lamports < rent_exempt {
+        system_program::transfer(CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+            },
+        ), rent_exempt - vault_lamports)?;
+    }
+
+    ctx.accounts.fee_split.set_inner(FeeSplit {
+        bump: ctx.bumps.fee_split,
+        vault_bump: ctx.bumps.fee_vault,
+        recipients,
+    });
+    ctx.accounts.config.fee_collector_sol = ctx.accounts.fee_vault.key();
+    ctx.accounts.config.fee_collector_usdc = ctx.accounts.fee_vault_usdc.key();
+    ctx.accounts.config.version += 1;
+    Ok(())
+}
+
+/// Pays out everything accrued in the fee vaults, split by weight. Permissionless.
+///
+/// Remaining accounts hold, for each recipient of the split in order, its wallet and its USDC
+/// token account.
+pub fn distribute_fees_ix<'info>(ctx: Context<'_, '_, 'info, 'info, DistributeFeesContext<'info>>) -> Result<()> {
+    let recipients = &ctx.accounts.fee_split.recipients;
+    require!(
+        ctx.remaining_accounts.len() == recipients.len() * ACCOUNTS_PER_RECIPIENT,
+        FeeSplitError::InvalidRecipientAccounts
+    );
+
+    let usdc_total = ctx.accounts.fee_vault_usdc.amount;
+    let native_total = ctx.accounts.fee_vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0));
+    let vault_seeds: &[&[u8]] = &[FeeSplit::VAULT_SEED_PREFIX, &[ctx.accounts.fee_split.vault_bump]];
+
+    let (mut usdc_paid, mut native_paid) = (0u64, 0u64);
+    for (i, (recipient, accounts)) in recipients.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_RECIPIENT)).enumerate() {
+        let (wallet, token_account) = (&accounts[0], &accounts[1]);
+        require_keys_eq!(wallet.key(), recipient.wallet, FeeSplitError::InvalidRecipientAccounts);
+        require_keys_eq!(token_account.key(), recipient.token_account, FeeSplitError::InvalidRecipientAccounts);
+
+        // the last recipient takes the rounding remainder
+        let share = |total: u64, paid: u64| if i + 1 == recipients.len() {
+            total - paid
+        } else {
+            (total as u128 * recipient.weight_bp as u128 / 10000) as u64
+        };
+        let usdc_amount = share(usdc_total, usdc_paid);
+        let native_amount = share(native_total, native_paid);
+        usdc_paid += usdc_amount;
+        native_paid += native_amount;
+
+        if usdc_amount > 0 {
+            token::transfer(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SplTransfer {
+                    from: ctx.accounts.fee_vault_usdc.to_account_info(),
+                    to: token_account.clone(),
+                    authority: ctx.accounts.fee_vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ), usdc_amount)?;
+        }
+        if native_amount > 0 {
+            system_program::transfer(CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    to: wallet.clone(),
+                },
+                &[vault_seeds],
+            ), native_amount)?;
+        }
+
+        emit_event!(ctx, FeePayout {
+            wallet: recipient.wallet,
+            token_account: recipient.token_account,
+            usdc_amount,
+            native_amount,
+        });
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplitContext<'info> {
+    #[account(mut, seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + FeeSplit::INIT_SPACE,
+        seeds = [FeeSplit::SEED_PREFIX],
+        bump,
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+
+    /// Seeds must be \["fee_vault"\].
+    #[account(
+        mut,
+        seeds = [FeeSplit::VAULT_SEED_PREFIX],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// Seeds must be \["fee_vault_usdc"\].
+    #[account(
+        init_if_needed,
+        payer = owner,
+        token::mint = usdc_mint,
+        token::authority = fee_vault,
+        seeds = [FeeSplit::USDC_VAULT_SEED_PREFIX],
+        bump,
+    )]
+    pub fee_vault_usdc: Box<Account<'info, TokenAccount>>,
+
+    pub usdc_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DistributeFeesContext<'info> {
+    #[account(
+        seeds = [FeeSplit::SEED_PREFIX],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+
+    #[account(
+        mut,
+        seeds = [FeeSplit::VAULT_SEED_PREFIX],
+        bump = fee_split.vault_bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FeeSplit::USDC_VAULT_SEED_PREFIX],
+        bump,
+    )]
+    pub fee_vault_usdc: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
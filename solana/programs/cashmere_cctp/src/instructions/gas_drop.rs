@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    self,
+    Mint,
+    Token,
+    TokenAccount,
+    Transfer as SplTransfer
+};
+use crate::{
+    state::{
+        Config,
+        GasDropEscrow,
+        GasPrice,
+        Relayer,
+        TransferReceipt,
+        TransferStatus,
+    },
+    events::{
+        GasDropAcknowledged,
+        GasDropRefunded,
+        GasPriceUpdated,
+    },
+    errors::{
+        GasDropError,
+        ReceiptError,
+    },
+    utils::{
+        emit_event,
+        MAX_GAS_PRICE_DECIMALS,
+    },
+};
+
+/// Creates the gas drop escrow of `destination_domain`.
+pub fn init_gas_drop_escrow_ix(ctx: Context<InitGasDropEscrowContext>, destination_domain: u32) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set parameters");
+    let escrow = &mut ctx.accounts.gas_drop_escrow;
+    escrow.bump = ctx.bumps.gas_drop_escrow;
+    escrow.destination_domain = destination_domain;
+    Ok(())
+}
+
+/// Posts the native token price of `domain`. Only callable by the gas oracle.
+pub fn set_gas_price_ix(ctx: Context<SetGasPriceContext>, domain: u32, price: u64, decimals: u8) -> Result<()> {
+    require!(price > 0 && decimals <= MAX_GAS_PRICE_DECIMALS, GasDropError::InvalidGasPrice);
+    ctx.accounts.gas_price.set_inner(GasPrice {
+        bump: ctx.bumps.gas_price,
+        domain,
+        price,
+        decimals,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+    emit_event!(ctx, GasPriceUpdated {
+        domain,
+        price,
+        decimals,
+    });
+    Ok(())
+}
+
+/// Acknowledges that the relayer that reported the mint of a transfer also delivered its gas drop
+/// on the destination chain, and pays the escrowed gas drop to that relayer.
+pub fn acknowledge_gas_drop_ix(
+    ctx: Context<AcknowledgeGasDropContext>,
+    _nonce: u64,
+    destination_tx_hash: [u8; 32],
+) -> Result<()> {
+    check_gas_drop_pending(&ctx.accounts.receipt)?;
+    require!(ctx.accounts.receipt.status == TransferStatus::Minted, GasDropError::NotMinted);
+
+    let (amount, fee_is_native) = (ctx.accounts.receipt.gas_drop_amount, ctx.accounts.receipt.fee_is_native);
+    release_gas_drop(
+        &mut ctx.accounts.gas_drop_escrow,
+        &ctx.accounts.gas_drop_escrow_usdc.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.relayer_token_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        amount,
+        fee_is_native,
+    )?;
+
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.status = TransferStatus::GasDelivered;
+    receipt.gas_drop_tx_hash = destination_tx_hash;
+    receipt.updated_at = Clock::get()?.unix_timestamp;
+
+    emit_event!(ctx, GasDropAcknowledged {
+        nonce: receipt.nonce,
+        relayer: ctx.accounts.relayer.authority,
+        destination_tx_hash,
+    });
+    Ok(())
+}
+
+/// Refunds the escrowed gas drop of a transfer to its user, once it has gone unacknowledged for
+/// `Config.gas_drop_refund_timeout`.
+pub fn claim_gas_drop_refund_ix(ctx: Context<ClaimGasDropRefundContext>, _nonce: u64) -> Result<()> {
+    check_gas_drop_pending(&ctx.accounts.receipt)?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= ctx.accounts.receipt.created_at.saturating_add(ctx.accounts.config.gas_drop_refund_timeout),
+        GasDropError::RefundNotDue
+    );
+
+    let (amount, fee_is_native) = (ctx.accounts.receipt.gas_drop_amount, ctx.accounts.receipt.fee_is_native);
+    release_gas_drop(
+        &mut ctx.accounts.gas_drop_escrow,
+        &ctx.accounts.gas_drop_escrow_usdc.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.owner_token_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        amount,
+        fee_is_native,
+    )?;
+
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.status = TransferStatus::Refunded;
+    receipt.updated_at = now;
+
+    emit_event!(ctx, GasDropRefunded {
+        nonce: receipt.nonce,
+        user: receipt.user,
+        amount,
+        fee_is_native,
+    });
+    Ok(())
+}
+
+/// Fails unless the transfer has a gas drop still held in escrow.
+fn check_gas_drop_pending(receipt: &TransferReceipt) -> Result<()> {
+    require!(receipt.gas_drop_amount > 0, GasDropError::NoGasDrop);
+    require!(
+        matches!(receipt.status, TransferStatus::Burned | TransferStatus::Minted),
+        ReceiptError::InvalidStatusTransition
+    );
+    Ok(())
+}
+
+/// Pays `amount` out of the escrow to `wallet` if `fee_is_native`, otherwise out of its USDC
+/// escrow to `token_account`, and takes it off the outstanding balance.
+fn release_gas_drop<'info>(
+    escrow: &mut Account<'info, GasDropEscrow>,
+    escrow_usdc: &AccountInfo<'info>,
+    wallet: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    fee_is_native: bool,
+) -> Result<()> {
+    let outstanding = escrow.outstanding_mut(fee_is_native);
+    *outstanding = outstanding.checked_sub(amount).ok_or(GasDropError::InsufficientEscrow)?;
+
+    if fee_is_native {
+        // the escrow is owned by this program, so lamports move without the system program
+        escrow.sub_lamports(amount)?;
+        wallet.add_lamports(amount)?;
+        Ok(())
+    } else {
+        let domain_bytes = escrow.destination_domain.to_le_bytes();
+        let escrow_seeds: &[&[u8]] = &[GasDropEscrow::SEED_PREFIX, &domain_bytes, &[escrow.bump]];
+        token::transfer(CpiContext::new_with_signer(
+            token_program.clone(),
+            SplTransfer {
+                from: escrow_usdc.clone(),
+                to: token_account.clone(),
+                authority: escrow.to_account_info(),
+            },
+            &[escrow_seeds],
+        ), amount)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(destination_domain: u32)]
+pub struct InitGasDropEscrowContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// Seeds must be \["gas_drop_escrow", destination_domain\].
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + GasDropEscrow::INIT_SPACE,
+        seeds = [GasDropEscrow::SEED_PREFIX, &destination_domain.to_le_bytes()],
+        bump,
+    )]
+    pub gas_drop_escrow: Box<Account<'info, GasDropEscrow>>,
+
+    /// Seeds must be \["gas_drop_escrow_usdc", destination_domain\].
+    #[account(
+        init_if_needed,
+        payer = owner,
+        token::mint = usdc_mint,
+        token::authority = gas_drop_escrow,
+        seeds = [GasDropEscrow::USDC_SEED_PREFIX, &destination_domain.to_le_bytes()],
+        bump,
+    )]
+    pub gas_drop_escrow_usdc: Box<Account<'info, TokenAccount>>,
+
+    pub usdc_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain: u32)]
+pub struct SetGasPriceContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// Seeds must be \["gas_price", domain\].
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = 8 + GasPrice::INIT_SPACE,
+        seeds = [GasPrice::SEED_PREFIX, &domain.to_le_bytes()],
+        bump,
+    )]
+    pub gas_price: Account<'info, GasPrice>,
+
+    #[account(
+        mut,
+        address = config.gas_oracle,
+    )]
+    pub oracle: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct AcknowledgeGasDropContext<'info> {
+    #[account(
+        mut,
+        seeds = [TransferReceipt::SEED_PREFIX, receipt.user.as_ref(), &nonce.to_le_bytes()],
+        bump = receipt.bump,
+        constraint = receipt.relayer == authority.key() @ GasDropError::NotMintingRelayer,
+    )]
+    pub receipt: Box<Account<'info, TransferReceipt>>,
+
+    /// Seeds must be \["relayer", authority\].
+    #[account(
+        seeds = [Relayer::SEED_PREFIX, authority.key().as_ref()],
+        bump = relayer.bump,
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    /// Receives SOL gas drops.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Receives USDC gas drops.
+    #[account(
+        mut,
+        token::mint = gas_drop_escrow_usdc.mint,
+        token::authority = authority,
+    )]
+    pub relayer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Seeds must be \["gas_drop_escrow", receipt.destination_domain\].
+    #[account(
+        mut,
+        seeds = [GasDropEscrow::SEED_PREFIX, &receipt.destination_domain.to_le_bytes()],
+        bump = gas_drop_escrow.bump,
+    )]
+    pub gas_drop_escrow: Box<Account<'info, GasDropEscrow>>,
+
+    /// Seeds must be \["gas_drop_escrow_usdc", receipt.destination_domain\].
+    #[account(
+        mut,
+        seeds = [GasDropEscrow::USDC_SEED_PREFIX, &receipt.destination_domain.to_le_bytes()],
+        bump,
+    )]
+    pub gas_drop_escrow_usdc: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct ClaimGasDropRefundContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [TransferReceipt::SEED_PREFIX, receipt.user.as_ref(), &nonce.to_le_bytes()],
+        bump = receipt.bump,
+        constraint = receipt.user == owner.key() @ ReceiptError::NotTransferUser,
+    )]
+    pub receipt: Box<Account<'info, TransferReceipt>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Receives USDC refunds.
+    #[account(
+        mut,
+        token::mint = gas_drop_escrow_usdc.mint,
+        token::authority = owner,
+    )]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Seeds must be \["gas_drop_escrow", receipt.destination_domain\].
+    #[account(
+        mut,
+        seeds = [GasDropEscrow::SEED_PREFIX, &receipt.destination_domain.to_le_bytes()],
+        bump = gas_drop_escrow.bump,
+    )]
+    pub gas_drop_escrow: Box<Account<'info, GasDropEscrow>>,
+
+    /// Seeds must be \["gas_drop_escrow_usdc", receipt.destination_domain\].
+    #[account(
+        mut,
+        seeds = [GasDropEscrow::USDC_SEED_PREFIX, &receipt.destination_domain.to_le_bytes()],
+        bump,
+    )]
+    pub gas_drop_escrow_usdc: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -1,11 +1,30 @@
 use std::mem;
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 use mem::size_of;
-use crate::state::{
-    Custodian,
-    Config,
+use crate::{
+    state::{
+        ChainFamily,
+        Custodian,
+        Config,
+    },
+    errors::ParamError,
 };
 
+/// Chain families of the CCTP domains live at the time of writing, indexed by domain.
+const KNOWN_CHAIN_FAMILIES: [ChainFamily; 11] = [
+    ChainFamily::Evm,    // 0: Ethereum
+    ChainFamily::Evm,    // 1: Avalanche
+    ChainFamily::Evm,    // 2: OP Mainnet
+    ChainFamily::Evm,    // 3: Arbitrum
+    ChainFamily::Noble,  // 4: Noble
+    ChainFamily::Solana, // 5: Solana
+    ChainFamily::Evm,    // 6: Base
+    ChainFamily::Evm,    // 7: Polygon PoS
+    ChainFamily::Sui,    // 8: Sui
+    ChainFamily::Aptos,  // 9: Aptos
+    ChainFamily::Evm,    // 10: Unichain
+];
+
 pub fn initialize_ix(
     ctx: Context<InitializeContext>,
     fee_collector_sol: Pubkey,
@@ -22,6 +41,7 @@ pub fn initialize_ix(
     ctx.accounts.config.nonce = 0;
     ctx.accounts.config.max_usdc_gas_drop = 100_000_000;
     ctx.accounts.config.max_native_gas_drop = [0u64; 32];
+    set_config_defaults(&mut ctx.accounts.config);
     ctx.accounts.custodian.set_inner(Custodian {
         bump: ctx.bumps.custodian,
     });
@@ -29,14 +49,71 @@ pub fn initialize_ix(
     Ok(())
 }
 
+/// Grows a config created before the fields that follow `max_native_gas_drop` to the current
+/// layout, paid for by the owner, and sets the added fields to their `initialize` defaults.
+pub fn migrate_config_ix(ctx: Context<MigrateConfigContext>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let space = 8 + Config::INIT_SPACE;
+    require!(config_info.data_len() < space, ParamError::ConfigUpToDate);
+    // the owner is the first field, so it reads the same in every layout
+    let owner = Pubkey::try_from(&config_info.try_borrow_data()?[8..40]).unwrap();
+    assert!(ctx.accounts.owner.key() == owner, "Only the caller can migrate the config");
+
+    let rent = Rent::get()?.minimum_balance(space).saturating_sub(config_info.lamports());
+    if rent > 0 {
+        system_program::transfer(CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: config_info.clone(),
+            },
+        ), rent)?;
+    }
+    // fields are only ever appended and the new bytes are zeroed, so the added fields
+    // deserialize to zero
+    config_info.resize(space)?;
+
+    let mut config = Config::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+    set_config_defaults(&mut config);
+    config.version += 1;
+    let mut data = config_info.try_borrow_mut_data()?;
+    config.try_serialize(&mut &mut data[..])
+}
+
+/// Sets the fields that follow `max_native_gas_drop` and are still zero to their non-zero
+/// defaults. Fields set by an earlier migration are left as they are.
+fn set_config_defaults(config: &mut Config) {
+    if config.chain_family.iter().all(|family| *family == ChainFamily::Unknown) {
+        config.chain_family[..KNOWN_CHAIN_FAMILIES.len()].copy_from_slice(&KNOWN_CHAIN_FAMILIES);
+    }
+    if config.volume_window == 0 {
+        config.volume_window = 86_400;
+    }
+    if config.guardian == Pubkey::default() {
+        config.guardian = config.owner;
+    }
+    if config.compliance == Pubkey::default() {
+        config.compliance = config.owner;
+    }
+    if config.breaker_window == 0 {
+        config.breaker_window = 3_600;
+    }
+    if config.gas_drop_refund_timeout == 0 {
+        config.gas_drop_refund_timeout = 86_400;
+    }
+    if config.gas_oracle == Pubkey::default() {
+        config.gas_oracle = config.owner;
+    }
+}
+
 #[derive(Accounts)]
 pub struct InitializeContext<'info> {
     #[account(init,
               payer = owner,
-              space = size_of::<Config>() + 8,
+              space = 8 + Config::INIT_SPACE,
               seeds = [b"config"],
               bump)]
-    pub config: Account<'info, Config>,
+    pub config: Box<Account<'info, Config>>,
 
     #[account(
         init,
@@ -52,3 +129,21 @@ pub struct InitializeContext<'info> {
 
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct MigrateConfigContext<'info> {
+    /// CHECK: Mutable. Seeds must be \["config"\]. Not deserialized, as it may predate the
+    /// current layout.
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        owner = crate::ID,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    self,
+    TokenAccount,
+    Transfer as SplTransfer
+};
+use crate::{
+    state::{
+        Config,
+        Integrator,
+    },
+    events::{
+        IntegratorRemoved,
+        IntegratorUpdated,
+    },
+    errors::{
+        IntegratorError,
+        ParamError,
+    },
+    utils::emit_event,
+};
+
+/// Registers `authority` as an integrator, or updates its registration.
+pub fn set_integrator_ix(
+    ctx: Context<SetIntegratorContext>,
+    authority: Pubkey,
+    fee_token_account: Pubkey,
+    max_fee_bp: u64,
+) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can set integrators");
+    require!(max_fee_bp <= 10000, ParamError::FeeTooHigh);
+    ctx.accounts.integrator.set_inner(Integrator {
+        bump: ctx.bumps.integrator,
+        authority,
+        fee_token_account,
+        max_fee_bp,
+    });
+    emit_event!(ctx, IntegratorUpdated {
+        authority,
+        fee_token_account,
+        max_fee_bp,
+    });
+    Ok(())
+}
+
+pub fn remove_integrator_ix(ctx: Context<RemoveIntegratorContext>, authority: Pubkey) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can remove integrators");
+    emit_event!(ctx, IntegratorRemoved {
+        authority,
+    });
+    Ok(())
+}
+
+/// Fails unless `integrator_fee_bp` is within the cap of `integrator`, and `integrator_token_account`
+/// is where it takes its fee. Without an integrator, the fee must be zero.
+pub(crate) fn check_integrator(
+    integrator: Option<&Integrator>,
+    integrator_token_account: Option<&AccountInfo>,
+    integrator_fee_bp: u64,
+) -> Result<()> {
+    match (integrator, integrator_token_account) {
+        (Some(integrator), Some(token_account)) => {
+            require_keys_eq!(token_account.key(), integrator.fee_token_account, IntegratorError::InvalidIntegratorTokenAccount);
+        }
+        (None, _) => {}
+        (Some(_), None) => return Err(IntegratorError::InvalidIntegratorTokenAccount.into()),
+    }
+    check_integrator_fee(integrator, integrator_fee_bp)
+}
+
+/// Fails unless `integrator_fee_bp` is within the cap of `integrator`. Without an integrator, the
+/// fee must be zero.
+pub(crate) fn check_integrator_fee(integrator: Option<&Integrator>, integrator_fee_bp: u64) -> Result<()> {
+    match integrator {
+        Some(integrator) => require!(integrator_fee_bp <= integrator.max_fee_bp, IntegratorError::IntegratorFeeTooHigh),
+        None => require!(integrator_fee_bp == 0, IntegratorError::MissingIntegrator),
+    }
+    Ok(())
+}
+
+/// Pays `integrator_fee` from the user's token account to the integrator.
+pub(crate) fn collect_integrator_fee<'info>(
+    owner: &AccountInfo<'info>,
+    owner_token_account: &AccountInfo<'info>,
+    integrator_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: &AccountInfo<'info>,
+    integrator_fee: u64,
+) -> Result<()> {
+    let Some(integrator_token_account) = integrator_token_account else {
+        return Ok(());
+    };
+    if integrator_fee == 0 {
+        return Ok(());
+    }
+    token::transfer(CpiContext::new(
+        token_program.clone(),
+        SplTransfer {
+            from: owner_token_account.clone(),
+            to: integrator_token_account.to_account_info(),
+            authority: owner.clone(),
+        },
+    ), integrator_fee)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct SetIntegratorContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Integrator::INIT_SPACE,
+        seeds = [Integrator::SEED_PREFIX, authority.as_ref()],
+        bump,
+    )]
+    pub integrator: Account<'info, Integrator>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct RemoveIntegratorContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [Integrator::SEED_PREFIX, authority.as_ref()],
+        bump = integrator.bump,
+    )]
+    pub integrator: Account<'info, Integrator>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
@@ -1,9 +1,31 @@
 pub mod initialize;
 pub mod admin;
-pub mod get_fee;
+pub mod denylist;
+pub mod integrator;
+pub mod fee_split;
+pub mod fee_discount;
+pub mod quote_transfer;
+pub mod preview_transfer;
 pub mod transfer;
+pub mod batch_transfer;
+pub mod transfer_large;
+pub mod replace_transfer;
+pub mod reclaim_event_account;
+pub mod receipt;
+pub mod gas_drop;
 
 pub use initialize::*;
 pub use admin::*;
-pub use get_fee::*;
+pub use denylist::*;
+pub use integrator::*;
+pub use fee_split::*;
+pub use fee_discount::*;
+pub use quote_transfer::*;
+pub use preview_transfer::*;
 pub use transfer::*;
+pub use batch_transfer::*;
+pub use transfer_large::*;
+pub use replace_transfer::*;
+pub use reclaim_event_account::*;
+pub use receipt::*;
+pub use gas_drop::*;
@@ -0,0 +1,359 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as sysvar};
+use anchor_spl::token::{
+    self,
+    Token,
+    TokenAccount,
+};
+use crate::{
+    instructions::integrator::check_integrator,
+    instructions::transfer::{
+        check_balances,
+        check_burn_limit,
+        check_cctp_accounts,
+        check_deadline,
+        check_gas_drop_accounts,
+        verify_transfer_signature,
+    },
+    utils::{
+        check_circuit_breaker,
+        check_gas_drop_price,
+        check_native_fee,
+        check_recipient_allowed,
+        check_sender_allowed,
+        check_volume_limits,
+        effective_fee_bp,
+        error_code,
+        quote_transfer,
+        validate_recipient,
+    },
+    state::{
+        Custodian,
+        Config,
+        DenylistEntry,
+        FeeDiscount,
+        GasDropEscrow,
+        GasPrice,
+        Integrator,
+        TransferPreview,
+        TransferReceipt,
+        UserState,
+        VolumeShard,
+    },
+    pyth::PriceUpdateV2,
+    cctp::{
+        LOCAL_DOMAIN,
+        TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+        MESSAGE_TRANSMITTER_PROGRAM_ID,
+        token_messenger_minter_program::LocalToken,
+    },
+    errors::TransferError,
+};
+
+/// Runs every check `transfer_ix` performs and reports the outcome instead of moving funds.
+/// Takes the same arguments and accounts as `transfer_ix`, so clients can simulate it with the
+/// transaction they are about to send.
+#[allow(clippy::too_many_arguments)]
+pub fn preview_transfer_ix(
+    ctx: Context<PreviewTransferContext>,
+    usdc_amount: u64,
+    destination_domain: u32,
+    recipient: [u8; 32],
+    solana_owner: [u8; 32],
+    fee: u64,
+    fee_usd: u64,
+    deadline: u64,
+    gas_drop_amount: u64,
+    gas_drop_native_amount: u64,
+    fee_is_native: bool,
+    limit_tier: u8,
+    integrator_fee_bp: u64,
+    quote_id: [u8; 16],
+    _client_memo: Option<[u8; 32]>,
+) -> Result<TransferPreview> {
+    if ctx.accounts.token_program.key() != token::ID {
+        return Err(TransferError::InvalidTokenProgram.into());
+    }
+
+    let accounts = &ctx.accounts;
+    let now = Clock::get()?.unix_timestamp;
+    let user_state = match accounts.user_state.owner == &crate::ID {
+        true => Some(UserState::try_deserialize(&mut &accounts.user_state.try_borrow_data()?[..])?),
+        false => None,
+    };
+    if let Some(receipt) = &accounts.receipt {
+        let nonce = user_state.as_ref().map(|user_state| user_state.nonce).unwrap_or_default() + 1;
+        let (expected, _) = Pubkey::find_program_address(
+            &[TransferReceipt::SEED_PREFIX, accounts.owner.key.as_ref(), &nonce.to_le_bytes()],
+            &crate::ID,
+        );
+        require_keys_eq!(receipt.key(), expected, ErrorCode::ConstraintSeeds);
+    }
+    let fee_bp = effective_fee_bp(
+        &accounts.config,
+        accounts.fee_discount.as_deref(),
+        user_state.as_ref().map(|user_state| user_state.cumulative_volume).unwrap_or_default(),
+        now,
+    );
+    let quote = quote_transfer(&accounts.config, fee_bp, usdc_amount, destination_domain, fee, gas_drop_amount, fee_is_native, integrator_fee_bp);
+
+    let signature_error = error_code(verify_transfer_signature(
+        &accounts.signature,
+        &accounts.config.signer_key,
+        crate::instruction::Transfer::DISCRIMINATOR,
+        destination_domain,
+        fee,
+        fee_usd,
+        deadline,
+        fee_is_native,
+        limit_tier,
+        accounts.integrator.as_ref().map(|integrator| integrator.authority).unwrap_or_default(),
+        integrator_fee_bp,
+        quote_id,
+    ));
+    let deadline_error = error_code(check_deadline(deadline));
+    let fee_error = quote.rejection_code;
+    let balance_error = error_code(check_balances(&accounts.owner_token_account, &accounts.owner, quote.total_usdc_cost, quote.total_native_cost));
+    let cctp_accounts_error = error_code(check_cctp_accounts(
+        destination_domain,
+        &accounts.message_transmitter.key(),
+        &accounts.token_messenger.key(),
+        &accounts.remote_token_messenger.key(),
+        &accounts.token_minter.key(),
+        &accounts.token_messenger_minter_sender_authority.key(),
+        &accounts.token_messenger_minter_event_authority.key(),
+    ));
+    let user_volume = user_state.map(|user_state| user_state.volume).unwrap_or_default();
+    let volume_error = error_code(check_volume_limits(
+        &accounts.config,
+        &accounts.volume_shard,
+        &user_volume,
+        destination_domain,
+        limit_tier,
+        quote.net_burn_amount,
+        now,
+    ));
+    let burn_limit_error = error_code(check_burn_limit(&accounts.local_token, quote.net_burn_amount));
+    let recipient_error = error_code(validate_recipient(&accounts.config, destination_domain, &recipient, &solana_owner, &accounts.owner_token_account.mint));
+    let denylist_error = error_code(check_sender_allowed(&accounts.sender_denylist_entry).and_then(|_| {
+        check_recipient_allowed(&accounts.recipient_denylist_entry)
+    }));
+    let integrator_error = error_code(check_integrator(
+        accounts.integrator.as_deref().map(|integrator| &**integrator),
+        accounts.integrator_token_account.as_ref().map(|account| account.as_ref()),
+        integrator_fee_bp,
+    ));
+    let circuit_breaker_error = error_code(check_circuit_breaker(&accounts.volume_shard, destination_domain));
+    let native_fee_error = error_code(check_native_fee(
+        &accounts.config,
+        accounts.sol_price_update.as_deref().map(|price_update| &**price_update),
+        fee,
+        fee_usd,
+        fee_is_native,
+        now,
+    ));
+    let gas_drop_error = error_code(check_gas_drop_accounts(
+        gas_drop_amount,
+        accounts.receipt.is_some(),
+        accounts.gas_drop_escrow.is_some() && accounts.gas_drop_escrow_usdc.is_some(),
+    ));
+    let gas_price_error = error_code(check_gas_drop_price(
+        &accounts.config,
+        accounts.gas_price.as_deref().map(|gas_price| &**gas_price),
+        accounts.native_gas_price.as_deref().map(|gas_price| &**gas_price),
+        gas_drop_amount,
+        gas_drop_native_amount,
+        fee_is_native,
+        now,
+    ));
+
+    Ok(TransferPreview {
+        quote,
+        signature_error,
+        deadline_error,
+        fee_error,
+        balance_error,
+        cctp_accounts_error,
+        volume_error,
+        circuit_breaker_error,
+        burn_limit_error,
+        recipient_error,
+        denylist_error,
+        integrator_error,
+        gas_drop_error,
+        gas_price_error,
+        native_fee_error,
+        valid: signature_error == 0
+            && deadline_error == 0
+            && fee_error == 0
+            && balance_error == 0
+            && cctp_accounts_error == 0
+            && volume_error == 0
+            && circuit_breaker_error == 0
+            && burn_limit_error == 0
+            && recipient_error == 0
+            && denylist_error == 0
+            && integrator_error == 0
+            && gas_drop_error == 0
+            && gas_price_error == 0
+            && native_fee_error == 0,
+    })
+}
+
+/// Mirrors [TransferContext](super::TransferContext) account for account. Accounts whose checks
+/// are reported rather than enforced are unchecked here, and nothing is created or written.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(usdc_amount: u64, destination_domain: u32, recipient: [u8; 32])]
+pub struct PreviewTransferContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = config.fee_collector_sol)]
+    pub fee_collector_sol_account: SystemAccount<'info>,
+    #[account(address = config.fee_collector_usdc)]
+    pub fee_collector_usdc_account: Account<'info, TokenAccount>,
+    #[account(address = config.gas_drop_collector_sol)]
+    pub gas_drop_collector_sol_account: SystemAccount<'info>,
+    #[account(address = config.gas_drop_collector_usdc)]
+    pub gas_drop_collector_usdc_account: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    /// CHECK: Seeds must be \["user", owner\]. Read for the user volume and nonce if it exists.
+    #[account(
+        seeds = [UserState::SEED_PREFIX, owner.key().as_ref()],
+        bump,
+    )]
+    pub user_state: UncheckedAccount<'info>,
+
+    /// Seeds must be \["volume_shard", VolumeShard::index(owner)\].
+    #[account(
+        seeds = [VolumeShard::SEED_PREFIX, &[VolumeShard::index(&owner.key())]],
+        bump = volume_shard.bump,
+    )]
+    pub volume_shard: Box<Account<'info, VolumeShard>>,
+
+    /// CHECK: Optional, created by `transfer`. Not read here. Must be the PDA \["receipt", owner,
+    /// nonce\], where nonce is the `user_state` nonce plus one; checked by the instruction, as
+    /// `user_state` may not exist yet.
+    pub receipt: Option<UncheckedAccount<'info>>,
+
+    /// Seeds must be \["gas_drop_escrow", destination_domain\] and
+    /// \["gas_drop_escrow_usdc", destination_domain\].
+    #[account(
+        seeds = [GasDropEscrow::SEED_PREFIX, &destination_domain.to_le_bytes()],
+        bump = gas_drop_escrow.bump,
+    )]
+    pub gas_drop_escrow: Option<Box<Account<'info, GasDropEscrow>>>,
+    #[account(
+        seeds = [GasDropEscrow::USDC_SEED_PREFIX, &destination_domain.to_le_bytes()],
+        bump,
+    )]
+    pub gas_drop_escrow_usdc: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Seeds must be \["gas_price", destination_domain\].
+    #[account(
+        seeds = [GasPrice::SEED_PREFIX, &destination_domain.to_le_bytes()],
+        bump = gas_price.bump,
+    )]
+    pub gas_price: Option<Box<Account<'info, GasPrice>>>,
+    /// Seeds must be \["gas_price", Solana domain\].
+    #[account(
+        seeds = [GasPrice::SEED_PREFIX, &LOCAL_DOMAIN.to_le_bytes()],
+        bump = native_gas_price.bump,
+    )]
+    pub native_gas_price: Option<Box<Account<'info, GasPrice>>>,
+
+    /// Pyth SOL/USD price update at `config.sol_price_feed`. Required for native fees while the
+    /// feed is set.
+    #[account(address = config.sol_price_feed)]
+    pub sol_price_update: Option<Box<Account<'info, PriceUpdateV2>>>,
+
+    /// Negotiated rate of `owner`, if any. Seeds must be \["fee_discount", owner\].
+    #[account(
+        seeds = [FeeDiscount::SEED_PREFIX, owner.key().as_ref()],
+        bump = fee_discount.bump,
+    )]
+    pub fee_discount: Option<Account<'info, FeeDiscount>>,
+
+    /// CHECK: Must not exist. Seeds must be \["denied_sender", owner\].
+    #[account(
+        seeds = [DenylistEntry::SENDER_SEED_PREFIX, owner.key().as_ref()],
+        bump,
+    )]
+    pub sender_denylist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Must not exist. Seeds must be \["denied_recipient", destination_domain, recipient\].
+    #[account(
+        seeds = [DenylistEntry::RECIPIENT_SEED_PREFIX, &destination_domain.to_le_bytes(), recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_denylist_entry: UncheckedAccount<'info>,
+
+    pub integrator: Option<Box<Account<'info, Integrator>>>,
+
+    /// CHECK: Reported in `integrator_error`.
+    pub integrator_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+
+    // cctp
+
+    #[account(
+        seeds = [Custodian::SEED_PREFIX],
+        bump = custodian.bump,
+    )]
+    custodian: Account<'info, Custodian>,
+
+    /// CHECK: Must be the mint of `local_token`.
+    #[account(address = local_token.mint)]
+    burn_token_mint: AccountInfo<'info>,
+
+    #[account(token::mint = burn_token_mint)]
+    burn_source: Account<'info, token::TokenAccount>,
+
+    /// CHECK: Created by `transfer`, not read here.
+    burn_token_account: UncheckedAccount<'info>,
+
+    local_token: Box<Account<'info, LocalToken>>,
+
+    /// CHECK: Must equal CCTP Token Messenger Minter program ID.
+    #[account(address = TOKEN_MESSENGER_MINTER_PROGRAM_ID)]
+    token_messenger_minter_program: UncheckedAccount<'info>,
+
+    /// CHECK: Must equal CCTP Message Transmitter program ID.
+    #[account(address = MESSAGE_TRANSMITTER_PROGRAM_ID)]
+    message_transmitter_program: UncheckedAccount<'info>,
+
+    /// CHECK: Reported in `cctp_accounts_error`.
+    token_messenger_minter_event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Reported in `cctp_accounts_error`.
+    message_transmitter: UncheckedAccount<'info>,
+
+    /// CHECK: Reported in `cctp_accounts_error`.
+    token_messenger: UncheckedAccount<'info>,
+
+    /// CHECK: Reported in `cctp_accounts_error`.
+    remote_token_messenger: UncheckedAccount<'info>,
+
+    /// CHECK: Reported in `cctp_accounts_error`.
+    token_minter: UncheckedAccount<'info>,
+
+    /// CHECK: Funded by `transfer`, not read here.
+    event_rent_payer: UncheckedAccount<'info>,
+
+    /// CHECK: Not used; must still be signed when sending `transfer`.
+    message_sent_event_data: UncheckedAccount<'info>,
+
+    /// CHECK: Reported in `cctp_accounts_error`.
+    token_messenger_minter_sender_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Safe because it's a sysvar account
+    #[account(address = sysvar::ID)]
+    pub signature: AccountInfo<'info>,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::{
+    instructions::integrator::check_integrator_fee,
+    state::{
+        Config,
+        FeeDiscount,
+        Integrator,
+        TransferQuote,
+        UserState,
+    },
+    utils::{
+        effective_fee_bp,
+        error_code,
+        quote_transfer,
+    },
+};
+
+/// Pass the `user_state` and `fee_discount` of `user`, where they exist, to quote with their
+/// discounted rate, and the `integrator` taking `integrator_fee_bp` to check it against its cap.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_transfer_ix(
+    ctx: Context<QuoteTransferContext>,
+    _user: Pubkey,
+    usdc_amount: u64,
+    destination_domain: u32,
+    fee: u64,
+    gas_drop_amount: u64,
+    fee_is_native: bool,
+    integrator_fee_bp: u64,
+) -> Result<TransferQuote> {
+    let fee_bp = effective_fee_bp(
+        &ctx.accounts.config,
+        ctx.accounts.fee_discount.as_deref(),
+        ctx.accounts.user_state.as_ref().map(|user_state| user_state.cumulative_volume).unwrap_or_default(),
+        Clock::get()?.unix_timestamp,
+    );
+    let mut quote = quote_transfer(&ctx.accounts.config, fee_bp, usdc_amount, destination_domain, fee, gas_drop_amount, fee_is_native, integrator_fee_bp);
+    if !quote.rejected {
+        quote.rejection_code = error_code(check_integrator_fee(ctx.accounts.integrator.as_deref(), integrator_fee_bp));
+        quote.rejected = quote.rejection_code != 0;
+    }
+    Ok(quote)
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct QuoteTransferContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// Rolling transfer volume of `user`. Seeds must be \["user", user\].
+    #[account(
+        seeds = [UserState::SEED_PREFIX, user.as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Option<Account<'info, UserState>>,
+
+    /// Negotiated rate of `user`, if any. Seeds must be \["fee_discount", user\].
+    #[account(
+        seeds = [FeeDiscount::SEED_PREFIX, user.as_ref()],
+        bump = fee_discount.bump,
+    )]
+    pub fee_discount: Option<Account<'info, FeeDiscount>>,
+
+    /// Integrator taking a share of the transfer, if any. Seeds must be \["integrator", authority\].
+    #[account(
+        seeds = [Integrator::SEED_PREFIX, integrator.authority.as_ref()],
+        bump = integrator.bump,
+    )]
+    pub integrator: Option<Account<'info, Integrator>>,
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        Config,
+        Relayer,
+        TransferReceipt,
+        TransferStatus,
+    },
+    events::{
+        RelayerAdded,
+        RelayerRemoved,
+        TransferStatusUpdated,
+    },
+    errors::ReceiptError,
+    utils::emit_event,
+};
+
+pub fn add_relayer_ix(ctx: Context<AddRelayerContext>, authority: Pubkey) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can add relayers");
+    ctx.accounts.relayer.set_inner(Relayer {
+        bump: ctx.bumps.relayer,
+        authority,
+    });
+    emit_event!(ctx, RelayerAdded {
+        authority,
+    });
+    Ok(())
+}
+
+pub fn remove_relayer_ix(ctx: Context<RemoveRelayerContext>, authority: Pubkey) -> Result<()> {
+    assert!(ctx.accounts.owner.key() == ctx.accounts.config.owner, "Only the caller can remove relayers");
+    emit_event!(ctx, RelayerRemoved {
+        authority,
+    });
+    Ok(())
+}
+
+/// Reports delivery progress of a transfer. Relayers can only mark a burned transfer minted here,
+/// which assigns its gas drop to them; gas drops are settled by `acknowledge_gas_drop` or
+/// `claim_gas_drop_refund`, which move escrowed funds.
+pub fn update_transfer_status_ix(ctx: Context<UpdateTransferStatusContext>, _nonce: u64, status: TransferStatus) -> Result<()> {
+    let receipt = &mut ctx.accounts.receipt;
+    require!(
+        status == TransferStatus::Minted && receipt.status == TransferStatus::Burned,
+        ReceiptError::InvalidStatusTransition
+    );
+    receipt.status = status;
+    receipt.relayer = ctx.accounts.relayer.authority;
+    receipt.updated_at = Clock::get()?.unix_timestamp;
+
+    emit_event!(ctx, TransferStatusUpdated {
+        nonce: receipt.nonce,
+        status,
+        authority: ctx.accounts.relayer.authority,
+    });
+    Ok(())
+}
+
+/// Closes the receipt of a final transfer and returns its rent to the user. The CCTP message
+/// account must have been reclaimed first, as reclaiming goes through the receipt.
+pub fn close_receipt_ix(ctx: Context<CloseReceiptContext>, _nonce: u64) -> Result<()> {
+    let receipt = &ctx.accounts.receipt;
+    require!(receipt.is_final(), ReceiptError::TransferNotFinal);
+    require!(receipt.message_reclaimed, ReceiptError::MessageNotReclaimed);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct AddRelayerContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Relayer::INIT_SPACE,
+        seeds = [Relayer::SEED_PREFIX, authority.as_ref()],
+        bump,
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct RemoveRelayerContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [Relayer::SEED_PREFIX, authority.as_ref()],
+        bump = relayer.bump,
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct UpdateTransferStatusContext<'info> {
+    #[account(
+        mut,
+        seeds = [TransferReceipt::SEED_PREFIX, receipt.user.as_ref(), &nonce.to_le_bytes()],
+        bump = receipt.bump,
+    )]
+    pub receipt: Box<Account<'info, TransferReceipt>>,
+
+    /// Seeds must be \["relayer", authority\].
+    #[account(
+        seeds = [Relayer::SEED_PREFIX, authority.key().as_ref()],
+        bump = relayer.bump,
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CloseReceiptContext<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [TransferReceipt::SEED_PREFIX, receipt.user.as_ref(), &nonce.to_le_bytes()],
+        bump = receipt.bump,
+        constraint = receipt.user == owner.key() @ ReceiptError::NotTransferUser,
+    )]
+    pub receipt: Box<Account<'info, TransferReceipt>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
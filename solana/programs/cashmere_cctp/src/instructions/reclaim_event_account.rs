@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use crate::{
+    instructions::transfer::{
+        drain_event_rent_payer,
+        EVENT_RENT_PAYER_SEED_PREFIX,
+    },
+    state::TransferReceipt,
+    events::EventAccountReclaimed,
+    cctp::{
+        pda,
+        MESSAGE_TRANSMITTER_PROGRAM_ID,
+        message_transmitter_program::{
+            MessageSent,
+            cpi::{
+                self as message_transmitter,
+                ReclaimEventAccount,
+                ReclaimEventAccountParams,
+            },
+        },
+    },
+    errors::{
+        CctpError,
+        ReceiptError,
+    },
+    utils::emit_event,
+};
+
+/// Closes an attested CCTP message account created by `transfer` or `replace_transfer` and returns
+/// its rent to the payer recorded in the receipt. Permissionless, so a crank can run it for users.
+pub fn reclaim_event_account_ix(
+    ctx: Context<ReclaimEventAccountContext>,
+    _nonce: u64,
+    attestation: Vec<u8>,
+) -> Result<()> {
+    let user = ctx.accounts.receipt.user;
+    let event_rent_payer_seeds: &[&[u8]] = &[EVENT_RENT_PAYER_SEED_PREFIX, user.as_ref(), &[ctx.bumps.event_rent_payer]];
+
+    message_transmitter::reclaim_event_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.message_transmitter_program.to_account_info(),
+            ReclaimEventAccount {
+                payee: ctx.accounts.event_rent_payer.to_account_info(),
+                message_transmitter: ctx.accounts.message_transmitter.to_account_info(),
+                message_sent_event_data: ctx.accounts.message_sent_event_data.to_account_info(),
+            },
+            &[event_rent_payer_seeds],
+        ),
+        ReclaimEventAccountParams { attestation },
+    )?;
+
+    let lamports = drain_event_rent_payer(
+        &ctx.accounts.event_rent_payer.to_account_info(),
+        &ctx.accounts.rent_payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &[event_rent_payer_seeds],
+    )?;
+
+    // the original message of a replaced transfer can be reclaimed too; only the current one
+    // counts towards closing the receipt
+    let receipt = &mut ctx.accounts.receipt;
+    if ctx.accounts.message_sent_event_data.key() == receipt.cctp_message {
+        receipt.message_reclaimed = true;
+    }
+
+    emit_event!(ctx, EventAccountReclaimed {
+        nonce: ctx.accounts.receipt.nonce,
+        cctp_message: ctx.accounts.message_sent_event_data.key(),
+        rent_payer: ctx.accounts.rent_payer.key(),
+        lamports,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct ReclaimEventAccountContext<'info> {
+    #[account(
+        mut,
+        seeds = [TransferReceipt::SEED_PREFIX, receipt.user.as_ref(), &nonce.to_le_bytes()],
+        bump = receipt.bump,
+    )]
+    pub receipt: Box<Account<'info, TransferReceipt>>,
+
+    #[account(
+        mut,
+        address = receipt.rent_payer,
+    )]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// Seeds must be \["event_rent_payer", receipt.user\].
+    #[account(
+        mut,
+        seeds = [EVENT_RENT_PAYER_SEED_PREFIX, receipt.user.as_ref()],
+        bump,
+    )]
+    event_rent_payer: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+
+    // cctp
+
+    /// Message account of this transfer, either the original or a replacement.
+    #[account(
+        mut,
+        constraint = message_sent_event_data.rent_payer == event_rent_payer.key() @ ReceiptError::MessageMismatch,
+    )]
+    message_sent_event_data: Box<Account<'info, MessageSent>>,
+
+    /// CHECK: Mutable. Seeds must be \["message_transmitter"\] (CCTP Message Transmitter program).
+    #[account(
+        mut,
+        address = pda::MESSAGE_TRANSMITTER @ CctpError::InvalidMessageTransmitter,
+    )]
+    message_transmitter: UncheckedAccount<'info>,
+
+    /// CHECK: Must equal CCTP Message Transmitter program ID.
+    #[account(address = MESSAGE_TRANSMITTER_PROGRAM_ID)]
+    message_transmitter_program: UncheckedAccount<'info>,
+}
@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use crate::{
+    instructions::transfer::{
+        drain_event_rent_payer,
+        fund_event_rent_payer,
+        EVENT_RENT_PAYER_SEED_PREFIX,
+    },
+    utils::{
+        check_recipient_allowed,
+        check_sender_allowed,
+        validate_recipient,
+        emit_event,
+    },
+    state::{
+        Config,
+        Custodian,
+        DenylistEntry,
+        TransferReceipt,
+        TransferStatus,
+    },
+    events::TransferReplaced,
+    cctp::{
+        pda,
+        LOCAL_DOMAIN,
+        TOKEN_MESSENGER_MINTER_PROGRAM_ID,
+        MESSAGE_TRANSMITTER_PROGRAM_ID,
+        message_transmitter_program::Message,
+        token_messenger_minter_program::cpi::{
+            self as token_messenger_minter,
+            ReplaceDepositForBurn,
+            ReplaceDepositForBurnParams,
+        },
+    },
+    errors::{
+        CctpError,
+        ReceiptError,
+    },
+};
+
+/// Replaces the recipient and/or destination caller of a burn made by `transfer_ix`, while it has
+/// not been minted. The custodian is the CCTP owner of every burn, so only this program can do
+/// it; the receipt ties the request to the user who made the transfer. `new_solana_owner` owns
+/// `new_recipient` for Solana destinations and is ignored otherwise.
+pub fn replace_transfer_ix(
+    ctx: Context<ReplaceTransferContext>,
+    _nonce: u64,
+    original_message: Vec<u8>,
+    original_attestation: Vec<u8>,
+    new_recipient: [u8; 32],
+    new_solana_owner: [u8; 32],
+    new_destination_caller: [u8; 32],
+) -> Result<()> {
+    // every burn has the custodian as owner, so the message must be bound to this receipt
+    let message = Message::parse(&original_message).ok_or(ReceiptError::MessageMismatch)?;
+    require!(
+        message.source_domain() == LOCAL_DOMAIN && message.nonce() == ctx.accounts.receipt.cctp_nonce,
+        ReceiptError::MessageMismatch
+    );
+    // the burn message body opens with its version, followed by the burned mint
+    let burn_token = message.message_body().get(4..36).ok_or(ReceiptError::MessageMismatch)?;
+    let burn_token = Pubkey::try_from(burn_token).unwrap();
+    validate_recipient(&ctx.accounts.config, message.destination_domain(), &new_recipient, &new_solana_owner, &burn_token)?;
+    check_sender_allowed(&ctx.accounts.sender_denylist_entry)?;
+    check_recipient_allowed(&ctx.accounts.recipient_denylist_entry)?;
+
+    let custodian_seeds: &[&[u8]] = &[Custodian::SEED_PREFIX, &[ctx.accounts.custodian.bump]];
+    let owner_key = ctx.accounts.owner.key();
+    let event_rent_payer_seeds: &[&[u8]] = &[EVENT_RENT_PAYER_SEED_PREFIX, owner_key.as_ref(), &[ctx.bumps.event_rent_payer]];
+    let cpi_seeds: &[&[&[u8]]] = &[custodian_seeds, event_rent_payer_seeds];
+
+    fund_event_rent_payer(
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.event_rent_payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_messenger_minter_program.to_account_info(),
+        ReplaceDepositForBurn {
+            owner: ctx.accounts.custodian.to_account_info(),
+            event_rent_payer: ctx.accounts.event_rent_payer.to_account_info(),
+            sender_authority_pda: ctx.accounts.token_messenger_minter_sender_authority.to_account_info(),
+            message_transmitter: ctx.accounts.message_transmitter.to_account_info(),
+            token_messenger: ctx.accounts.token_messenger.to_account_info(),
+            message_sent_event_data: ctx.accounts.message_sent_event_data.to_account_info(),
+            message_transmitter_program: ctx.accounts.message_transmitter_program.to_account_info(),
+            token_messenger_minter_program: ctx.accounts.token_messenger_minter_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            event_authority: ctx.accounts.token_messenger_minter_event_authority.to_account_info(),
+        },
+        cpi_seeds,
+    );
+
+    let cctp_nonce = token_messenger_minter::replace_deposit_for_burn(cpi_ctx, ReplaceDepositForBurnParams {
+        original_message,
+        original_attestation,
+        new_destination_caller,
+        new_mint_recipient: new_recipient,
+    })?;
+
+    drain_event_rent_payer(
+        &ctx.accounts.event_rent_payer.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &[event_rent_payer_seeds],
+    )?;
+
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.cctp_nonce = cctp_nonce;
+    receipt.cctp_message = ctx.accounts.message_sent_event_data.key();
+    receipt.recipient = new_recipient;
+    receipt.message_reclaimed = false;
+    receipt.updated_at = Clock::get()?.unix_timestamp;
+
+    emit_event!(ctx, TransferReplaced {
+        nonce: receipt.nonce,
+        user: receipt.user,
+        cctp_nonce,
+        new_recipient,
+        new_solana_owner,
+        new_destination_caller,
+        cctp_message: receipt.cctp_message,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nonce: u64, original_message: Vec<u8>, original_attestation: Vec<u8>, new_recipient: [u8; 32])]
+pub struct ReplaceTransferContext<'info> {
+    #[account(seeds=[b"config"], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [TransferReceipt::SEED_PREFIX, receipt.user.as_ref(), &nonce.to_le_bytes()],
+        bump = receipt.bump,
+        constraint = receipt.user == owner.key() @ ReceiptError::NotTransferUser,
+        constraint = receipt.status == TransferStatus::Burned @ ReceiptError::TransferNotPending,
+    )]
+    pub receipt: Box<Account<'info, TransferReceipt>>,
+
+    /// The user who made the transfer. Pays rent for the replacement message, which is returned to
+    /// the receipt's rent payer on reclaim.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Must not exist. Seeds must be \["denied_sender", owner\].
+    #[account(
+        seeds = [DenylistEntry::SENDER_SEED_PREFIX, owner.key().as_ref()],
+        bump,
+    )]
+    pub sender_denylist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Must not exist. Seeds must be \["denied_recipient", receipt.destination_domain,
+    /// new_recipient\].
+    #[account(
+        seeds = [DenylistEntry::RECIPIENT_SEED_PREFIX, &receipt.destination_domain.to_le_bytes(), new_recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_denylist_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+
+    // cctp
+
+    /// This program's emitter authority.
+    ///
+    /// Seeds must be \["emitter"\].
+    #[account(
+        seeds = [Custodian::SEED_PREFIX],
+        bump = custodian.bump,
+    )]
+    custodian: Account<'info, Custodian>,
+
+    /// CHECK: Must equal CCTP Token Messenger Minter program ID.
+    #[account(address = TOKEN_MESSENGER_MINTER_PROGRAM_ID)]
+    token_messenger_minter_program: UncheckedAccount<'info>,
+
+    /// CHECK: Must equal CCTP Message Transmitter program ID.
+    #[account(address = MESSAGE_TRANSMITTER_PROGRAM_ID)]
+    message_transmitter_program: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds must be \["__event_authority"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MESSENGER_MINTER_EVENT_AUTHORITY @ CctpError::InvalidEventAuthority)]
+    token_messenger_minter_event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mutable. Seeds must be \["message_transmitter"\] (CCTP Message Transmitter program).
+    #[account(
+        mut,
+        address = pda::MESSAGE_TRANSMITTER @ CctpError::InvalidMessageTransmitter,
+    )]
+    message_transmitter: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds must be \["token_messenger"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MESSENGER @ CctpError::InvalidTokenMessenger)]
+    token_messenger: UncheckedAccount<'info>,
+
+    /// Pays for the replacement message account, like in `transfer`.
+    ///
+    /// Seeds must be \["event_rent_payer", owner\].
+    #[account(
+        mut,
+        seeds = [EVENT_RENT_PAYER_SEED_PREFIX, owner.key().as_ref()],
+        bump,
+    )]
+    event_rent_payer: SystemAccount<'info>,
+
+    /// CHECK: Mutable signer to create the replacement CCTP message.
+    #[account(mut)]
+    message_sent_event_data: Signer<'info>,
+
+    /// CHECK: Seeds must be \["sender_authority"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MESSENGER_MINTER_SENDER_AUTHORITY @ CctpError::InvalidSenderAuthority)]
+    token_messenger_minter_sender_authority: UncheckedAccount<'info>,
+}
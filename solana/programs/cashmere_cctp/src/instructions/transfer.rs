@@ -1,7 +1,4 @@
-use anchor_lang::{prelude::*, solana_program::{
-    self,
-    sysvar::instructions as sysvar,
-}, system_program};
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as sysvar, system_program};
 use anchor_spl::token::{
     self,
     Token,
@@ -10,36 +7,356 @@ use anchor_spl::token::{
 };
 use borsh::{BorshSerialize, to_vec};
 use crate::{
+    instructions::integrator::{
+        check_integrator,
+        collect_integrator_fee,
+    },
     utils::{
+        emit_event,
         verify_ed25519_ix,
-        calculate_fee,
+        quote_transfer,
+        record_outflow,
+        check_recipient_allowed,
+        check_sender_allowed,
+        check_gas_drop_price,
+        check_native_fee,
+        effective_fee_bp,
+        record_volume,
+        validate_recipient,
     },
     state::{
         Custodian,
         Config,
+        DenylistEntry,
+        FeeDiscount,
+        GasDropEscrow,
+        GasPrice,
+        Integrator,
+        TransferReceipt,
+        TransferStatus,
+        UserState,
+        VolumeShard,
+    },
+    pyth::PriceUpdateV2,
+    events::{
+        CircuitBreakerTripped,
+        TransferEvent,
+        TransferEventV2,
+        VolumeLimitReached,
     },
-    events::TransferEvent,
     cctp::{
+        pda,
+        LOCAL_DOMAIN,
         TOKEN_MESSENGER_MINTER_PROGRAM_ID,
         MESSAGE_TRANSMITTER_PROGRAM_ID,
+        message_transmitter_program::Message,
         token_messenger_minter_program::{
             LocalToken,
             cpi::{
+                self as token_messenger_minter,
                 DepositForBurn,
                 DepositForBurnParams,
             },
         },
     },
-    errors::TransferError,
+    errors::{
+        TransferError,
+        CctpError,
+        GasDropError,
+    },
 };
 
+pub const CUSTODY_SEED_PREFIX: &[u8] = b"__custody";
+pub const EVENT_RENT_PAYER_SEED_PREFIX: &[u8] = b"event_rent_payer";
+
+/// Size of the CCTP message account created for a burn: discriminator, rent payer, message length
+/// prefix, message header and burn message body.
+const MESSAGE_SENT_SPACE: usize = 8 + 32 + 4 + Message::HEADER_LEN + 132;
+
 #[derive(BorshSerialize)]
-struct TransferParams {
+struct TransferParams<'a> {
+    /// Discriminator of the instruction the quote is for, so a `transfer` quote cannot be used
+    /// with `transfer_large`.
+    instruction: &'a [u8],
     local_domain: u32,
     destination_domain: u32,
     fee: u64,
+    fee_usd: u64,
+    deadline: u64,
+    fee_is_native: bool,
+    limit_tier: u8,
+    integrator: Pubkey,
+    integrator_fee_bp: u64,
+    quote_id: [u8; 16],
+}
+
+/// Checks the backend signature over the transfer parameters, carried by the ed25519 instruction
+/// preceding this one. `instruction` is the discriminator of the instruction being quoted,
+/// `fee_usd` the micro-USD value of a native `fee`, and `integrator` the default pubkey for
+/// transfers without one.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_transfer_signature(
+    signature: &AccountInfo,
+    signer_key: &[u8; 32],
+    instruction: &[u8],
+    destination_domain: u32,
+    fee: u64,
+    fee_usd: u64,
     deadline: u64,
     fee_is_native: bool,
+    limit_tier: u8,
+    integrator: Pubkey,
+    integrator_fee_bp: u64,
+    quote_id: [u8; 16],
+) -> Result<()> {
+    let msg = TransferParams {
+        instruction,
+        local_domain: LOCAL_DOMAIN,
+        destination_domain,
+        fee,
+        fee_usd,
+        deadline,
+        fee_is_native,
+        limit_tier,
+        integrator,
+        integrator_fee_bp,
+        quote_id,
+    };
+    let msg_bytes = to_vec(&msg)?;
+    verify_ed25519_ix(signature, &msg_bytes, signer_key)
+}
+
+pub(crate) fn check_deadline(deadline: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp as u64 <= deadline, TransferError::DeadlineExpired);
+    Ok(())
+}
+
+/// Fails if a gas drop comes without a receipt or without the gas drop escrow of its domain.
+pub(crate) fn check_gas_drop_accounts(gas_drop_amount: u64, has_receipt: bool, has_escrow: bool) -> Result<()> {
+    if gas_drop_amount > 0 {
+        require!(has_receipt, GasDropError::MissingReceipt);
+        require!(has_escrow, GasDropError::MissingEscrow);
+    }
+    Ok(())
+}
+
+/// Fails before the CCTP burn would, if `amount` is over the per-message burn limit of `local_token`.
+pub(crate) fn check_burn_limit(local_token: &LocalToken, amount: u64) -> Result<()> {
+    require!(amount <= local_token.burn_limit_per_message, TransferError::BurnLimitExceeded);
+    Ok(())
+}
+
+pub(crate) fn check_balances(owner_token_account: &TokenAccount, owner: &AccountInfo, usdc_cost: u64, native_cost: u64) -> Result<()> {
+    require!(owner_token_account.amount >= usdc_cost, TransferError::InsufficientUsdcBalance);
+    require!(owner.lamports() >= native_cost, TransferError::NativeAmountTooLow);
+    Ok(())
+}
+
+/// Adds `amount` to the user's cumulative volume and records it against the global, destination
+/// domain and user volume limits, failing if any would be exceeded. Returns `VolumeLimitReached`
+/// for every limit this transfer reaches.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn track_volume(
+    config: &Config,
+    shard: &mut VolumeShard,
+    user_state: &mut UserState,
+    user: Pubkey,
+    user_state_bump: u8,
+    destination_domain: u32,
+    limit_tier: u8,
+    amount: u64,
+) -> Result<Vec<VolumeLimitReached>> {
+    if user_state.user == Pubkey::default() {
+        user_state.bump = user_state_bump;
+        user_state.user = user;
+    }
+    user_state.cumulative_volume = user_state.cumulative_volume.saturating_add(amount);
+    let now = Clock::get()?.unix_timestamp;
+    record_volume(config, shard, &mut user_state.volume, user, destination_domain, limit_tier, amount, now)
+}
+
+/// Records `amount` against the circuit breaker of `destination_domain`, failing if the domain is
+/// paused. Returns `CircuitBreakerTripped` if this transfer trips it.
+pub(crate) fn track_outflow(
+    config: &Config,
+    shard: &mut VolumeShard,
+    destination_domain: u32,
+    amount: u64,
+) -> Result<Option<CircuitBreakerTripped>> {
+    let clock = Clock::get()?;
+    record_outflow(config, shard, destination_domain, amount, clock.unix_timestamp, clock.slot)
+}
+
+/// Same checks as the address constraints on [TransferContext], for callers that must not abort.
+pub(crate) fn check_cctp_accounts(
+    destination_domain: u32,
+    message_transmitter: &Pubkey,
+    token_messenger: &Pubkey,
+    remote_token_messenger: &Pubkey,
+    token_minter: &Pubkey,
+    sender_authority: &Pubkey,
+    event_authority: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(*message_transmitter, pda::MESSAGE_TRANSMITTER, CctpError::InvalidMessageTransmitter);
+    require_keys_eq!(*token_messenger, pda::TOKEN_MESSENGER, CctpError::InvalidTokenMessenger);
+    require_keys_eq!(*remote_token_messenger, pda::remote_token_messenger(destination_domain), CctpError::InvalidRemoteTokenMessenger);
+    require_keys_eq!(*token_minter, pda::TOKEN_MINTER, CctpError::InvalidTokenMinter);
+    require_keys_eq!(*sender_authority, pda::TOKEN_MESSENGER_MINTER_SENDER_AUTHORITY, CctpError::InvalidSenderAuthority);
+    require_keys_eq!(*event_authority, pda::TOKEN_MESSENGER_MINTER_EVENT_AUTHORITY, CctpError::InvalidEventAuthority);
+    Ok(())
+}
+
+/// Moves the rent of one CCTP message account from `owner` to the user's event rent payer PDA,
+/// which then pays for the message account in place of `owner`.
+pub(crate) fn fund_event_rent_payer<'info>(
+    owner: &AccountInfo<'info>,
+    event_rent_payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = Rent::get()?.minimum_balance(MESSAGE_SENT_SPACE);
+    system_program::transfer(CpiContext::new(
+        system_program.clone(),
+        system_program::Transfer {
+            from: owner.clone(),
+            to: event_rent_payer.clone(),
+        },
+    ), lamports)
+}
+
+/// Returns whatever the event rent payer PDA holds to `destination`, leaving it empty.
+pub(crate) fn drain_event_rent_payer<'info>(
+    event_rent_payer: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let lamports = event_rent_payer.lamports();
+    if lamports > 0 {
+        system_program::transfer(CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Transfer {
+                from: event_rent_payer.clone(),
+                to: destination.clone(),
+            },
+            signer_seeds,
+        ), lamports)?;
+    }
+    Ok(lamports)
+}
+
+/// Accounts fees and gas drops are collected with.
+pub(crate) struct FeeAccounts<'info> {
+    pub owner: AccountInfo<'info>,
+    pub owner_token_account: AccountInfo<'info>,
+    pub fee_collector_sol: AccountInfo<'info>,
+    pub fee_collector_usdc: AccountInfo<'info>,
+    pub gas_drop_collector_sol: AccountInfo<'info>,
+    pub gas_drop_collector_usdc: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> FeeAccounts<'info> {
+    pub fn collect(&self, usdc_fee: u64, native_fee: u64, gas_drop_amount: u64, fee_is_native: bool) -> Result<()> {
+        // collect fee in USDC
+        token::transfer(CpiContext::new(
+            self.token_program.clone(),
+            SplTransfer {
+                from: self.owner_token_account.clone(),
+                to: self.fee_collector_usdc.clone(),
+                authority: self.owner.clone(),
+            },
+        ), usdc_fee)?;
+
+        if fee_is_native {
+            // collect fee in SOL
+            system_program::transfer(CpiContext::new(
+                self.system_program.clone(),
+                system_program::Transfer {
+                    from: self.owner.clone(),
+                    to: self.fee_collector_sol.clone(),
+                },
+            ), native_fee)?;
+            // collect gas drop in SOL
+            if gas_drop_amount > 0 {
+                system_program::transfer(CpiContext::new(
+                    self.system_program.clone(),
+                    system_program::Transfer {
+                        from: self.owner.clone(),
+                        to: self.gas_drop_collector_sol.clone(),
+                    },
+                ), gas_drop_amount)?;
+            }
+        } else {
+            // collect gas drop in USDC
+            if gas_drop_amount > 0 {
+                token::transfer(CpiContext::new(
+                    self.token_program.clone(),
+                    SplTransfer {
+                        from: self.owner_token_account.clone(),
+                        to: self.gas_drop_collector_usdc.clone(),
+                        authority: self.owner.clone(),
+                    },
+                ), gas_drop_amount)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// CCTP accounts shared by every burn out of the custody token account.
+pub(crate) struct BurnAccounts<'info> {
+    pub custodian: AccountInfo<'info>,
+    pub burn_token_account: AccountInfo<'info>,
+    pub burn_token_mint: AccountInfo<'info>,
+    pub local_token: AccountInfo<'info>,
+    pub sender_authority: AccountInfo<'info>,
+    pub message_transmitter: AccountInfo<'info>,
+    pub token_messenger: AccountInfo<'info>,
+    pub token_minter: AccountInfo<'info>,
+    pub event_authority: AccountInfo<'info>,
+    pub message_transmitter_program: AccountInfo<'info>,
+    pub token_messenger_minter_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> BurnAccounts<'info> {
+    /// Burns `params.amount` out of the custody token account and returns the CCTP nonce.
+    /// `signer_seeds` must sign for the custodian and `event_rent_payer`, if it is a PDA.
+    pub fn deposit_for_burn(
+        &self,
+        event_rent_payer: AccountInfo<'info>,
+        remote_token_messenger: AccountInfo<'info>,
+        message_sent_event_data: AccountInfo<'info>,
+        params: DepositForBurnParams,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<u64> {
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_messenger_minter_program.clone(),
+            DepositForBurn {
+                owner: self.custodian.clone(),
+                event_rent_payer,
+                sender_authority_pda: self.sender_authority.clone(),
+                burn_token_account: self.burn_token_account.clone(),
+                message_transmitter: self.message_transmitter.clone(),
+                token_messenger: self.token_messenger.clone(),
+                remote_token_messenger,
+                token_minter: self.token_minter.clone(),
+                local_token: self.local_token.clone(),
+                burn_token_mint: self.burn_token_mint.clone(),
+                message_sent_event_data,
+                message_transmitter_program: self.message_transmitter_program.clone(),
+                token_messenger_minter_program: self.token_messenger_minter_program.clone(),
+                token_program: self.token_program.clone(),
+                system_program: self.system_program.clone(),
+                event_authority: self.event_authority.clone(),
+            },
+            signer_seeds,
+        );
+        token_messenger_minter::deposit_for_burn(cpi_ctx, params)
+    }
 }
 
 /*
@@ -49,6 +366,7 @@ fee structure:
 - gas drop is taken either in USDC or SOL, depending on `fee_is_native`
 */
 
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_ix(
     ctx: Context<TransferContext>,
     usdc_amount: u64,
@@ -56,89 +374,121 @@ pub fn transfer_ix(
     recipient: [u8; 32],
     solana_owner: [u8; 32],
     fee: u64,
+    fee_usd: u64,
     deadline: u64,
     gas_drop_amount: u64,
+    gas_drop_native_amount: u64,
     fee_is_native: bool,
+    limit_tier: u8,
+    integrator_fee_bp: u64,
+    quote_id: [u8; 16],
+    client_memo: Option<[u8; 32]>,
 ) -> Result<()> {
     if ctx.accounts.token_program.key() != token::ID {
         return Err(TransferError::InvalidTokenProgram.into());
     }
 
-    let msg = TransferParams {
-        local_domain: 5,
+    let integrator = ctx.accounts.integrator.as_ref().map(|integrator| integrator.authority).unwrap_or_default();
+    verify_transfer_signature(
+        &ctx.accounts.signature,
+        &ctx.accounts.config.signer_key,
+        crate::instruction::Transfer::DISCRIMINATOR,
         destination_domain,
         fee,
+        fee_usd,
         deadline,
         fee_is_native,
-    };
-    let msg_bytes = to_vec(&msg)?;
-    let ed25519_ix = &ctx.accounts.signature.to_account_info();
-    match verify_ed25519_ix(ed25519_ix, &msg_bytes, &ctx.accounts.config.signer_key) {
-        Err(e) => return Err(e),
-        _ => {},
-    };
+        limit_tier,
+        integrator,
+        integrator_fee_bp,
+        quote_id,
+    )?;
+    check_deadline(deadline)?;
+    check_integrator(
+        ctx.accounts.integrator.as_deref().map(|integrator| &**integrator),
+        ctx.accounts.integrator_token_account.as_ref().map(|account| account.as_ref()),
+        integrator_fee_bp,
+    )?;
 
-    let clock = Clock::get()?;
-    if clock.unix_timestamp as u64 > deadline {
-        return Err(TransferError::DeadlineExpired.into());
+    let now = Clock::get()?.unix_timestamp;
+    let fee_bp = effective_fee_bp(
+        &ctx.accounts.config,
+        ctx.accounts.fee_discount.as_deref(),
+        ctx.accounts.user_state.cumulative_volume,
+        now,
+    );
+    let quote = quote_transfer(&ctx.accounts.config, fee_bp, usdc_amount, destination_domain, fee, gas_drop_amount, fee_is_native, integrator_fee_bp);
+    quote.validate()?;
+    validate_recipient(&ctx.accounts.config, destination_domain, &recipient, &solana_owner, ctx.accounts.burn_token_mint.key)?;
+    check_sender_allowed(&ctx.accounts.sender_denylist_entry)?;
+    check_recipient_allowed(&ctx.accounts.recipient_denylist_entry)?;
+    check_native_fee(
+        &ctx.accounts.config,
+        ctx.accounts.sol_price_update.as_deref().map(|price_update| &**price_update),
+        fee,
+        fee_usd,
+        fee_is_native,
+        now,
+    )?;
+    check_gas_drop_price(
+        &ctx.accounts.config,
+        ctx.accounts.gas_price.as_deref().map(|gas_price| &**gas_price),
+        ctx.accounts.native_gas_price.as_deref().map(|gas_price| &**gas_price),
+        gas_drop_amount,
+        gas_drop_native_amount,
+        fee_is_native,
+        now,
+    )?;
+    check_gas_drop_accounts(
+        gas_drop_amount,
+        ctx.accounts.receipt.is_some(),
+        ctx.accounts.gas_drop_escrow.is_some() && ctx.accounts.gas_drop_escrow_usdc.is_some(),
+    )?;
+    check_burn_limit(&ctx.accounts.local_token, quote.net_burn_amount)?;
+    check_balances(&ctx.accounts.owner_token_account, &ctx.accounts.owner, quote.total_usdc_cost, quote.total_native_cost)?;
+    let usdc_fee_amount = quote.usdc_fee - quote.integrator_fee;
+
+    let amount = quote.net_burn_amount;
+
+    for event in track_volume(
+        &ctx.accounts.config,
+        &mut ctx.accounts.volume_shard,
+        &mut ctx.accounts.user_state,
+        ctx.accounts.owner.key(),
+        ctx.bumps.user_state,
+        destination_domain,
+        limit_tier,
+        amount,
+    )? {
+        emit_event!(ctx, event);
     }
-
-    let usdc_fee_amount = calculate_fee(usdc_amount, ctx.accounts.config.fee_bp, if fee_is_native { 0 } else { fee });
-    require!(usdc_amount >= usdc_fee_amount, TransferError::FeeExceedsAmount);
-    if fee_is_native {
-        let native_gas_drop_limit = ctx.accounts.config.max_native_gas_drop[destination_domain as usize];
-        require!(native_gas_drop_limit == 0 || gas_drop_amount <= native_gas_drop_limit, TransferError::GasDropLimitExceeded);
-    } else {
-        let usdc_gas_drop_limit = ctx.accounts.config.max_usdc_gas_drop;
-        require!(usdc_gas_drop_limit == 0 || gas_drop_amount <= usdc_gas_drop_limit, TransferError::GasDropLimitExceeded);
+    if let Some(event) = track_outflow(&ctx.accounts.config, &mut ctx.accounts.volume_shard, destination_domain, amount)? {
+        emit_event!(ctx, event);
     }
 
-    // collect fee in USDC
-    token::transfer(CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        SplTransfer {
-            from: ctx.accounts.owner_token_account.to_account_info(),
-            to: ctx.accounts.fee_collector_usdc_account.to_account_info(),
-            authority: ctx.accounts.owner.to_account_info(),
-        },
-    ), usdc_fee_amount)?;
-    
-    if fee_is_native {
-        // collect fee in SOL
-        system_program::transfer(CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.owner.to_account_info(),
-                to: ctx.accounts.fee_collector_sol_account.to_account_info(),
-            },
-        ), fee)?;
-        // collect gas drop in SOL
-        if gas_drop_amount > 0 {
-            system_program::transfer(CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.owner.to_account_info(),
-                    to: ctx.accounts.gas_drop_collector_sol_account.to_account_info(),
-                },
-            ), gas_drop_amount)?;
-        }
-    } else {
-        // collect gas drop in USDC
-        if gas_drop_amount > 0 {
-            token::transfer(CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                SplTransfer {
-                    from: ctx.accounts.owner_token_account.to_account_info(),
-                    to: ctx.accounts.gas_drop_collector_usdc_account.to_account_info(),
-                    authority: ctx.accounts.owner.to_account_info(),
-                },
-            ), gas_drop_amount)?;
-        }
+    // gas drops are held in the domain's escrow until a relayer claims them or the user is
+    // refunded, both of which go through the receipt
+    let mut fee_accounts = ctx.accounts.fee_accounts();
+    if let (true, Some(escrow), Some(escrow_usdc)) = (
+        gas_drop_amount > 0,
+        ctx.accounts.gas_drop_escrow.as_mut(),
+        &ctx.accounts.gas_drop_escrow_usdc,
+    ) {
+        let outstanding = escrow.outstanding_mut(fee_is_native);
+        *outstanding += gas_drop_amount;
+        fee_accounts.gas_drop_collector_sol = escrow.to_account_info();
+        fee_accounts.gas_drop_collector_usdc = escrow_usdc.to_account_info();
     }
+    fee_accounts.collect(usdc_fee_amount, quote.native_fee, gas_drop_amount, fee_is_native)?;
+    collect_integrator_fee(
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.owner_token_account.to_account_info(),
+        ctx.accounts.integrator_token_account.as_ref(),
+        &ctx.accounts.token_program.to_account_info(),
+        quote.integrator_fee,
+    )?;
 
-    let amount = usdc_amount - usdc_fee_amount;
-
-    let custodian_seeds: &[&[&[u8]]] = &[&[Custodian::SEED_PREFIX, &[ctx.accounts.custodian.bump]]];
+    let custodian_seeds: &[&[u8]] = &[Custodian::SEED_PREFIX, &[ctx.accounts.custodian.bump]];
 
     // transfer the rest
     token::transfer(CpiContext::new_with_signer(
@@ -148,62 +498,110 @@ pub fn transfer_ix(
             to: ctx.accounts.burn_token_account.to_account_info(),
             authority: ctx.accounts.custodian.to_account_info(),
         },
-        custodian_seeds,
+        &[custodian_seeds],
     ), amount)?;
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_messenger_minter_program.to_account_info(),
-        DepositForBurn {
-            owner: ctx.accounts.custodian.to_account_info(),
-            event_rent_payer: ctx.accounts.owner.to_account_info(),
-            sender_authority_pda: ctx.accounts.token_messenger_minter_sender_authority.to_account_info(),
-            burn_token_account: ctx.accounts.burn_token_account.to_account_info(),
-            message_transmitter: ctx.accounts.message_transmitter.to_account_info(),
-            token_messenger: ctx.accounts.token_messenger.to_account_info(),
-            remote_token_messenger: ctx.accounts.remote_token_messenger.to_account_info(),
-            token_minter: ctx.accounts.token_minter.to_account_info(),
-            local_token: ctx.accounts.local_token.to_account_info(),
-            burn_token_mint: ctx.accounts.burn_token_mint.to_account_info(),
-            message_sent_event_data: ctx.accounts.message_sent_event_data.to_account_info(),
-            message_transmitter_program: ctx.accounts.message_transmitter_program.to_account_info(),
-            token_messenger_minter_program: ctx.accounts.token_messenger_minter_program.to_account_info(),
-            token_program: ctx.accounts.token_program.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            event_authority: ctx.accounts.token_messenger_minter_event_authority.to_account_info(),
-        },
-        custodian_seeds,
-    );
-
-    let args = DepositForBurnParams {
-        amount,
-        destination_domain,
-        mint_recipient: recipient,
+    let owner_key = ctx.accounts.owner.key();
+    let event_rent_payer_seeds: &[&[u8]] = &[EVENT_RENT_PAYER_SEED_PREFIX, owner_key.as_ref(), &[ctx.bumps.event_rent_payer]];
+
+    // with a receipt, the message rent is paid through the event rent payer so this program can
+    // reclaim it later; without one, the user pays it and reclaims it from CCTP directly
+    let message_rent_payer = if ctx.accounts.receipt.is_some() {
+        fund_event_rent_payer(
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.event_rent_payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        ctx.accounts.event_rent_payer.to_account_info()
+    } else {
+        ctx.accounts.owner.to_account_info()
     };
 
-    const ANCHOR_IX_SELECTOR: [u8; 8] = [215, 60, 61, 46, 114, 55, 128, 176];
-
-    solana_program::program::invoke_signed(
-        &solana_program::instruction::Instruction {
-            program_id: TOKEN_MESSENGER_MINTER_PROGRAM_ID,
-            accounts: cpi_ctx.to_account_metas(None),
-            data: (ANCHOR_IX_SELECTOR, args).try_to_vec()?,
+    let cctp_nonce = ctx.accounts.burn_accounts().deposit_for_burn(
+        message_rent_payer,
+        ctx.accounts.remote_token_messenger.to_account_info(),
+        ctx.accounts.message_sent_event_data.to_account_info(),
+        DepositForBurnParams {
+            amount,
+            destination_domain,
+            mint_recipient: recipient,
         },
-        &cpi_ctx.to_account_infos(),
-        cpi_ctx.signer_seeds,
+        &[custodian_seeds, event_rent_payer_seeds],
     )?;
 
-    ctx.accounts.config.nonce += 1;
+    ctx.accounts.user_state.nonce += 1;
+    let nonce = ctx.accounts.user_state.nonce;
+
+    if let Some(receipt) = ctx.accounts.receipt.as_mut() {
+        drain_event_rent_payer(
+            &ctx.accounts.event_rent_payer.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[event_rent_payer_seeds],
+        )?;
+
+        receipt.set_inner(TransferReceipt {
+            bump: ctx.bumps.receipt.unwrap_or_default(),
+            nonce,
+            user: owner_key,
+            cctp_nonce,
+            cctp_message: ctx.accounts.message_sent_event_data.key(),
+            rent_payer: owner_key,
+            amount,
+            destination_domain,
+            recipient,
+            gas_drop_amount,
+            gas_drop_native_amount,
+            fee_is_native,
+            status: TransferStatus::Burned,
+            relayer: Pubkey::default(),
+            gas_drop_tx_hash: [0; 32],
+            message_reclaimed: false,
+            created_at: now,
+            updated_at: now,
+        });
+    }
 
-    emit!(TransferEvent {
+    emit_event!(ctx, TransferEvent {
         destination_domain,
-        nonce: ctx.accounts.config.nonce,
+        nonce,
         recipient,
         solana_owner,
         user: ctx.accounts.owner.key(),
         amount,
         gas_drop_amount,
-        cctp_nonce: -1,
+        source_domain: LOCAL_DOMAIN,
+        cctp_nonce: cctp_nonce as i64,
+        fee_is_native,
+        cctp_message: ctx.accounts.message_sent_event_data.key(),
+        usdc_fee: quote.usdc_fee,
+        integrator,
+        integrator_fee: quote.integrator_fee,
+    });
+
+    emit_event!(ctx, TransferEventV2 {
+        nonce,
+        user: ctx.accounts.owner.key(),
+        source_domain: LOCAL_DOMAIN,
+        destination_domain,
+        recipient,
+        solana_owner,
+        burn_token_mint: ctx.accounts.burn_token_mint.key(),
+        source_token_account: ctx.accounts.owner_token_account.key(),
+        gross_amount: usdc_amount,
+        fee_usdc: quote.usdc_fee,
+        fee_native: quote.native_fee,
+        fee_bp: quote.fee_bp,
+        gas_drop_amount,
+        gas_drop_native_amount,
         fee_is_native,
+        net_burned: amount,
+        integrator,
+        integrator_fee: quote.integrator_fee,
+        config_version: ctx.accounts.config.version,
+        quote_id,
+        client_memo,
+        cctp_nonce,
         cctp_message: ctx.accounts.message_sent_event_data.key(),
     });
 
@@ -214,13 +612,48 @@ pub fn transfer_ix(
             destination: ctx.accounts.owner.to_account_info(),
             authority: ctx.accounts.custodian.to_account_info(),
         },
-        custodian_seeds,
+        &[custodian_seeds],
     ))
 }
 
+impl<'info> TransferContext<'info> {
+    pub(crate) fn fee_accounts(&self) -> FeeAccounts<'info> {
+        FeeAccounts {
+            owner: self.owner.to_account_info(),
+            owner_token_account: self.owner_token_account.to_account_info(),
+            fee_collector_sol: self.fee_collector_sol_account.to_account_info(),
+            fee_collector_usdc: self.fee_collector_usdc_account.to_account_info(),
+            gas_drop_collector_sol: self.gas_drop_collector_sol_account.to_account_info(),
+            gas_drop_collector_usdc: self.gas_drop_collector_usdc_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+
+    pub(crate) fn burn_accounts(&self) -> BurnAccounts<'info> {
+        BurnAccounts {
+            custodian: self.custodian.to_account_info(),
+            burn_token_account: self.burn_token_account.to_account_info(),
+            burn_token_mint: self.burn_token_mint.to_account_info(),
+            local_token: self.local_token.to_account_info(),
+            sender_authority: self.token_messenger_minter_sender_authority.to_account_info(),
+            message_transmitter: self.message_transmitter.to_account_info(),
+            token_messenger: self.token_messenger.to_account_info(),
+            token_minter: self.token_minter.to_account_info(),
+            event_authority: self.token_messenger_minter_event_authority.to_account_info(),
+            message_transmitter_program: self.message_transmitter_program.to_account_info(),
+            token_messenger_minter_program: self.token_messenger_minter_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+}
+
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(usdc_amount: u64, destination_domain: u32, recipient: [u8; 32])]
 pub struct TransferContext<'info> {
-    #[account(mut, seeds=[b"config"], bump)]
+    #[account(seeds=[b"config"], bump)]
     pub config: Box<Account<'info, Config>>,
 
     #[account(mut)]
@@ -250,6 +683,104 @@ pub struct TransferContext<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
 
+    /// Rolling transfer volume and nonce of `owner`. Seeds must be \["user", owner\].
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + UserState::INIT_SPACE,
+        seeds = [UserState::SEED_PREFIX, owner.key().as_ref()],
+        bump,
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    /// Global and domain volume windows and circuit breakers `owner` is tracked in. Seeds must be
+    /// \["volume_shard", VolumeShard::index(owner)\].
+    #[account(
+        mut,
+        seeds = [VolumeShard::SEED_PREFIX, &[VolumeShard::index(&owner.key())]],
+        bump = volume_shard.bump,
+    )]
+    pub volume_shard: Box<Account<'info, VolumeShard>>,
+
+    /// Optional receipt for this transfer. Seeds must be \["receipt", owner, nonce\], where nonce
+    /// is the `TransferEvent.nonce` this transfer will emit (`user_state.nonce + 1`). Required to
+    /// replace the transfer or reclaim its message rent through this program.
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TransferReceipt::INIT_SPACE,
+        seeds = [TransferReceipt::SEED_PREFIX, owner.key().as_ref(), &(user_state.nonce + 1).to_le_bytes()],
+        bump,
+    )]
+    pub receipt: Option<Box<Account<'info, TransferReceipt>>>,
+
+    /// Gas drop escrow of `destination_domain`, which takes the gas drop instead of the gas drop
+    /// collectors. Required, with `receipt`, if the transfer has a gas drop. Seeds must be
+    /// \["gas_drop_escrow", destination_domain\] and \["gas_drop_escrow_usdc", destination_domain\].
+    #[account(
+        mut,
+        seeds = [GasDropEscrow::SEED_PREFIX, &destination_domain.to_le_bytes()],
+        bump = gas_drop_escrow.bump,
+    )]
+    pub gas_drop_escrow: Option<Box<Account<'info, GasDropEscrow>>>,
+    #[account(
+        mut,
+        seeds = [GasDropEscrow::USDC_SEED_PREFIX, &destination_domain.to_le_bytes()],
+        bump,
+    )]
+    pub gas_drop_escrow_usdc: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Gas prices of `destination_domain` and of Solana, checked against the gas drop payment.
+    /// Required with a gas drop while the check is on; the Solana price only if `fee_is_native`.
+    /// Seeds must be \["gas_price", domain\].
+    #[account(
+        seeds = [GasPrice::SEED_PREFIX, &destination_domain.to_le_bytes()],
+        bump = gas_price.bump,
+    )]
+    pub gas_price: Option<Box<Account<'info, GasPrice>>>,
+    #[account(
+        seeds = [GasPrice::SEED_PREFIX, &LOCAL_DOMAIN.to_le_bytes()],
+        bump = native_gas_price.bump,
+    )]
+    pub native_gas_price: Option<Box<Account<'info, GasPrice>>>,
+
+    /// Pyth SOL/USD price update at `config.sol_price_feed`. Required for native fees while the
+    /// feed is set.
+    #[account(address = config.sol_price_feed)]
+    pub sol_price_update: Option<Box<Account<'info, PriceUpdateV2>>>,
+
+    /// Negotiated rate of `owner`, if any. Seeds must be \["fee_discount", owner\].
+    #[account(
+        seeds = [FeeDiscount::SEED_PREFIX, owner.key().as_ref()],
+        bump = fee_discount.bump,
+    )]
+    pub fee_discount: Option<Account<'info, FeeDiscount>>,
+
+    /// CHECK: Must not exist. Seeds must be \["denied_sender", owner\].
+    #[account(
+        seeds = [DenylistEntry::SENDER_SEED_PREFIX, owner.key().as_ref()],
+        bump,
+    )]
+    pub sender_denylist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Must not exist. Seeds must be \["denied_recipient", destination_domain, recipient\].
+    #[account(
+        seeds = [DenylistEntry::RECIPIENT_SEED_PREFIX, &destination_domain.to_le_bytes(), recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_denylist_entry: UncheckedAccount<'info>,
+
+    /// Integrator taking a share of this transfer, if any. Seeds must be \["integrator", authority\].
+    #[account(
+        seeds = [Integrator::SEED_PREFIX, integrator.authority.as_ref()],
+        bump = integrator.bump,
+    )]
+    pub integrator: Option<Box<Account<'info, Integrator>>>,
+
+    /// Integrator's fee token account. Required with `integrator`.
+    #[account(mut)]
+    pub integrator_token_account: Option<Account<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 
@@ -290,13 +821,14 @@ pub struct TransferContext<'info> {
     /// It just acts as a conduit to allow this program to be the transfer initiator in the CCTP
     /// message.
     ///
-    /// Seeds must be \["__custody"\].
+    /// Seeds must be \["__custody", owner, nonce\], where nonce is `user_state.nonce + 1`, so
+    /// every transfer gets an account of its own.
     #[account(
         init,
         payer = owner,
         token::mint = burn_token_mint,
         token::authority = custodian,
-        seeds = [b"__custody"],
+        seeds = [CUSTODY_SEED_PREFIX, owner.key().as_ref(), &(user_state.nonce + 1).to_le_bytes()],
         bump,
     )]
     burn_token_account: Account<'info, token::TokenAccount>,
@@ -316,27 +848,46 @@ pub struct TransferContext<'info> {
     message_transmitter_program: UncheckedAccount<'info>,
 
     /// CHECK: Seeds must be \["__event_authority"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MESSENGER_MINTER_EVENT_AUTHORITY @ CctpError::InvalidEventAuthority)]
     token_messenger_minter_event_authority: UncheckedAccount<'info>,
 
     /// CHECK: Mutable. Seeds must be \["message_transmitter"\] (CCTP Message Transmitter program).
-    #[account(mut)]
+    #[account(
+        mut,
+        address = pda::MESSAGE_TRANSMITTER @ CctpError::InvalidMessageTransmitter,
+    )]
     message_transmitter: UncheckedAccount<'info>,
 
     /// CHECK: Seeds must be \["token_messenger"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MESSENGER @ CctpError::InvalidTokenMessenger)]
     token_messenger: UncheckedAccount<'info>,
 
     /// CHECK: Seeds must be \["remote_token_messenger"\, remote_domain.to_string()] (CCTP Token
-    /// Messenger Minter program).
+    /// Messenger Minter program), where remote_domain is `destination_domain`.
+    #[account(address = pda::remote_token_messenger(destination_domain) @ CctpError::InvalidRemoteTokenMessenger)]
     remote_token_messenger: UncheckedAccount<'info>,
 
     /// CHECK: Seeds must be \["token_minter"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MINTER @ CctpError::InvalidTokenMinter)]
     token_minter: UncheckedAccount<'info>,
 
+    /// Pays for the CCTP message account with lamports moved from `owner`, so its rent can later
+    /// be reclaimed to `owner` without `owner` signing.
+    ///
+    /// Seeds must be \["event_rent_payer", owner\].
+    #[account(
+        mut,
+        seeds = [EVENT_RENT_PAYER_SEED_PREFIX, owner.key().as_ref()],
+        bump,
+    )]
+    event_rent_payer: SystemAccount<'info>,
+
     /// CHECK: Mutable signer to create CCTP message.
     #[account(mut)]
     message_sent_event_data: Signer<'info>,
 
     /// CHECK: Seeds must be \["sender_authority"\] (CCTP Token Messenger Minter program).
+    #[account(address = pda::TOKEN_MESSENGER_MINTER_SENDER_AUTHORITY @ CctpError::InvalidSenderAuthority)]
     token_messenger_minter_sender_authority: UncheckedAccount<'info>,
 
     /// CHECK: Safe because it's a sysvar account
@@ -0,0 +1,254 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    self,
+    Transfer as SplTransfer
+};
+use crate::{
+    instructions::{
+        batch_transfer::BatchTransferContext,
+        transfer::{
+            check_balances,
+            check_deadline,
+            track_outflow,
+            track_volume,
+            verify_transfer_signature,
+        },
+    },
+    utils::{
+        emit_event,
+        check_unconstrained_recipient_allowed,
+        check_sender_allowed,
+        check_native_fee,
+        effective_fee_bp,
+        quote_transfer,
+        validate_recipient,
+    },
+    state::Custodian,
+    events::{
+        LargeTransferEvent,
+        TransferEvent,
+        TransferEventV2,
+    },
+    cctp::{
+        pda,
+        LOCAL_DOMAIN,
+        token_messenger_minter_program::cpi::DepositForBurnParams,
+    },
+    errors::{
+        TransferError,
+        CctpError,
+    },
+};
+
+/// Most CCTP messages a single `transfer_large` can split into.
+pub const MAX_SPLIT_MESSAGES: usize = 8;
+
+/// Same as `transfer_ix`, for amounts over the CCTP burn limit per message. The net amount is
+/// burned in as many messages as the limit requires, with the fees charged once. Each message
+/// gets its own nonce and events, like a `batch_transfer` leg, summed up by a
+/// `LargeTransferEvent`. Gas drops are not supported, as there is no receipt to escrow them
+/// against.
+///
+/// Takes the accounts of `batch_transfer`. Remaining accounts hold the remote token messenger of
+/// `destination_domain` and the denylist PDA of `recipient`, followed by a fresh signer for each
+/// CCTP message account. The user pays message rent directly and can reclaim it from CCTP.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_large_ix<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchTransferContext<'info>>,
+    usdc_amount: u64,
+    destination_domain: u32,
+    recipient: [u8; 32],
+    solana_owner: [u8; 32],
+    fee: u64,
+    fee_usd: u64,
+    deadline: u64,
+    fee_is_native: bool,
+    limit_tier: u8,
+    quote_id: [u8; 16],
+) -> Result<()> {
+    if ctx.accounts.token_program.key() != token::ID {
+        return Err(TransferError::InvalidTokenProgram.into());
+    }
+
+    verify_transfer_signature(
+        &ctx.accounts.signature,
+        &ctx.accounts.config.signer_key,
+        crate::instruction::TransferLarge::DISCRIMINATOR,
+        destination_domain,
+        fee,
+        fee_usd,
+        deadline,
+        fee_is_native,
+        limit_tier,
+        Pubkey::default(),
+        0,
+        quote_id,
+    )?;
+    check_deadline(deadline)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    check_native_fee(
+        &ctx.accounts.config,
+        ctx.accounts.sol_price_update.as_deref().map(|price_update| &**price_update),
+        fee,
+        fee_usd,
+        fee_is_native,
+        now,
+    )?;
+
+    let fee_bp = effective_fee_bp(
+        &ctx.accounts.config,
+        ctx.accounts.fee_discount.as_deref(),
+        ctx.accounts.user_state.cumulative_volume,
+        now,
+    );
+    let quote = quote_transfer(&ctx.accounts.config, fee_bp, usdc_amount, destination_domain, fee, 0, fee_is_native, 0);
+    quote.validate()?;
+    validate_recipient(&ctx.accounts.config, destination_domain, &recipient, &solana_owner, ctx.accounts.burn_token_mint.key)?;
+    check_sender_allowed(&ctx.accounts.sender_denylist_entry)?;
+    check_balances(&ctx.accounts.owner_token_account, &ctx.accounts.owner, quote.total_usdc_cost, quote.total_native_cost)?;
+    let amount = quote.net_burn_amount;
+
+    let burn_limit = ctx.accounts.local_token.burn_limit_per_message;
+    require!(burn_limit > 0, TransferError::BurnLimitExceeded);
+    let pieces = amount.div_ceil(burn_limit) as usize;
+    require!(pieces <= MAX_SPLIT_MESSAGES, TransferError::BurnLimitExceeded);
+    require!(ctx.remaining_accounts.len() == 2 + pieces, TransferError::InvalidBatchAccounts);
+
+    let remote_token_messenger = &ctx.remaining_accounts[0];
+    require_keys_eq!(
+        remote_token_messenger.key(),
+        pda::remote_token_messenger(destination_domain),
+        CctpError::InvalidRemoteTokenMessenger
+    );
+    check_unconstrained_recipient_allowed(&ctx.remaining_accounts[1], destination_domain, &recipient)?;
+
+    for event in track_volume(
+        &ctx.accounts.config,
+        &mut ctx.accounts.volume_shard,
+        &mut ctx.accounts.user_state,
+        ctx.accounts.owner.key(),
+        ctx.bumps.user_state,
+        destination_domain,
+        limit_tier,
+        amount,
+    )? {
+        emit_event!(ctx, event);
+    }
+    if let Some(event) = track_outflow(&ctx.accounts.config, &mut ctx.accounts.volume_shard, destination_domain, amount)? {
+        emit_event!(ctx, event);
+    }
+
+    ctx.accounts.fee_accounts().collect(quote.usdc_fee, quote.native_fee, 0, fee_is_native)?;
+
+    let custodian_seeds: &[&[u8]] = &[Custodian::SEED_PREFIX, &[ctx.accounts.custodian.bump]];
+
+    token::transfer(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        SplTransfer {
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            to: ctx.accounts.burn_token_account.to_account_info(),
+            authority: ctx.accounts.custodian.to_account_info(),
+        },
+        &[custodian_seeds],
+    ), amount)?;
+
+    let burn_accounts = ctx.accounts.burn_accounts();
+    let first_nonce = ctx.accounts.user_state.nonce + 1;
+    let mut cctp_nonces = Vec::with_capacity(pieces);
+    let mut cctp_messages = Vec::with_capacity(pieces);
+    let mut remaining = amount;
+    for (i, message_sent_event_data) in ctx.remaining_accounts[2..].iter().enumerate() {
+        require!(message_sent_event_data.is_signer, TransferError::InvalidBatchAccounts);
+        let piece = remaining.min(burn_limit);
+        remaining -= piece;
+
+        let cctp_nonce = burn_accounts.deposit_for_burn(
+            ctx.accounts.owner.to_account_info(),
+            remote_token_messenger.clone(),
+            message_sent_event_data.clone(),
+            DepositForBurnParams {
+                amount: piece,
+                destination_domain,
+                mint_recipient: recipient,
+            },
+            &[custodian_seeds],
+        )?;
+
+        ctx.accounts.user_state.nonce += 1;
+        let nonce = ctx.accounts.user_state.nonce;
+
+        // fees are charged once, so they are reported with the first message
+        let (usdc_fee, native_fee) = if i == 0 { (quote.usdc_fee, quote.native_fee) } else { (0, 0) };
+
+        emit_event!(ctx, TransferEvent {
+            destination_domain,
+            nonce,
+            recipient,
+            solana_owner,
+            user: ctx.accounts.owner.key(),
+            amount: piece,
+            gas_drop_amount: 0,
+            source_domain: LOCAL_DOMAIN,
+            cctp_nonce: cctp_nonce as i64,
+            fee_is_native,
+            cctp_message: message_sent_event_data.key(),
+            usdc_fee,
+            integrator: Pubkey::default(),
+            integrator_fee: 0,
+        });
+
+        emit_event!(ctx, TransferEventV2 {
+            nonce,
+            user: ctx.accounts.owner.key(),
+            source_domain: LOCAL_DOMAIN,
+            destination_domain,
+            recipient,
+            solana_owner,
+            burn_token_mint: ctx.accounts.burn_token_mint.key(),
+            source_token_account: ctx.accounts.owner_token_account.key(),
+            gross_amount: usdc_fee + piece,
+            fee_usdc: usdc_fee,
+            fee_native: native_fee,
+            fee_bp: quote.fee_bp,
+            gas_drop_amount: 0,
+            gas_drop_native_amount: 0,
+            fee_is_native,
+            net_burned: piece,
+            integrator: Pubkey::default(),
+            integrator_fee: 0,
+            config_version: ctx.accounts.config.version,
+            quote_id,
+            client_memo: None,
+            cctp_nonce,
+            cctp_message: message_sent_event_data.key(),
+        });
+
+        cctp_nonces.push(cctp_nonce);
+        cctp_messages.push(message_sent_event_data.key());
+    }
+
+    emit_event!(ctx, LargeTransferEvent {
+        destination_domain,
+        first_nonce,
+        last_nonce: ctx.accounts.user_state.nonce,
+        recipient,
+        solana_owner,
+        user: ctx.accounts.owner.key(),
+        amount,
+        fee_is_native,
+        source_domain: LOCAL_DOMAIN,
+        cctp_nonces,
+        cctp_messages,
+    });
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.burn_token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.custodian.to_account_info(),
+        },
+        &[custodian_seeds],
+    ))
+}
@@ -3,10 +3,19 @@ pub mod state;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod pyth;
 pub mod utils;
 
 use anchor_lang::prelude::*;
 use instructions::*;
+use state::{
+    ChainFamily,
+    Discount,
+    FeeRecipient,
+    TransferPreview,
+    TransferQuote,
+    TransferStatus,
+};
 
 declare_id!("4zNrqVWiVDUr26FJeVoXKVzA2jxMHErW1ZUmJo11DNiX");
 
@@ -26,6 +35,10 @@ pub mod cashmere_cctp {
         initialize_ix(ctx, fee_collector_sol, fee_collector_usdc, gas_drop_collector_sol, gas_drop_collector_usdc)
     }
 
+    pub fn migrate_config(ctx: Context<MigrateConfigContext>) -> Result<()> {
+        migrate_config_ix(ctx)
+    }
+
     // admin ixs
 
     pub fn set_fee_bp(ctx: Context<ConfigContext>, fee_bp: u64) -> Result<()> {
@@ -52,18 +65,193 @@ pub mod cashmere_cctp {
         set_max_native_gas_drop_ix(ctx, destination_domain, max_gas)
     }
 
+    pub fn set_transfer_amount_limits(
+        ctx: Context<ConfigContext>,
+        destination_domain: u32,
+        min_amount: u64,
+        max_amount: u64,
+    ) -> Result<()> {
+        set_transfer_amount_limits_ix(ctx, destination_domain, min_amount, max_amount)
+    }
+
+    pub fn set_chain_family(ctx: Context<ConfigContext>, destination_domain: u32, chain_family: ChainFamily) -> Result<()> {
+        set_chain_family_ix(ctx, destination_domain, chain_family)
+    }
+
+    pub fn set_fee_tier(ctx: Context<ConfigContext>, index: u8, min_volume: u64, discount_bp: u64) -> Result<()> {
+        set_fee_tier_ix(ctx, index, min_volume, discount_bp)
+    }
+
+    pub fn set_volume_window(ctx: Context<ConfigContext>, volume_window: i64) -> Result<()> {
+        set_volume_window_ix(ctx, volume_window)
+    }
+
+    pub fn set_global_volume_limit(ctx: Context<ConfigContext>, limit: u64) -> Result<()> {
+        set_global_volume_limit_ix(ctx, limit)
+    }
+
+    pub fn set_domain_volume_limit(ctx: Context<ConfigContext>, destination_domain: u32, limit: u64) -> Result<()> {
+        set_domain_volume_limit_ix(ctx, destination_domain, limit)
+    }
+
+    pub fn set_user_volume_limit(ctx: Context<ConfigContext>, limit_tier: u8, limit: u64) -> Result<()> {
+        set_user_volume_limit_ix(ctx, limit_tier, limit)
+    }
+
+    pub fn set_guardian(ctx: Context<ConfigContext>, guardian: Pubkey) -> Result<()> {
+        set_guardian_ix(ctx, guardian)
+    }
+
+    pub fn set_circuit_breaker(ctx: Context<ConfigContext>, breaker_window: i64, max_transfers_per_slot: u32) -> Result<()> {
+        set_circuit_breaker_ix(ctx, breaker_window, max_transfers_per_slot)
+    }
+
+    pub fn set_breaker_outflow_limit(ctx: Context<ConfigContext>, destination_domain: u32, limit: u64) -> Result<()> {
+        set_breaker_outflow_limit_ix(ctx, destination_domain, limit)
+    }
+
+    pub fn set_gas_drop_refund_timeout(ctx: Context<ConfigContext>, timeout: i64) -> Result<()> {
+        set_gas_drop_refund_timeout_ix(ctx, timeout)
+    }
+
+    pub fn set_gas_oracle(ctx: Context<ConfigContext>, gas_oracle: Pubkey) -> Result<()> {
+        set_gas_oracle_ix(ctx, gas_oracle)
+    }
+
+    pub fn set_gas_price_check(ctx: Context<ConfigContext>, tolerance_bp: u64, max_age: i64) -> Result<()> {
+        set_gas_price_check_ix(ctx, tolerance_bp, max_age)
+    }
+
+    pub fn set_sol_price_feed(
+        ctx: Context<ConfigContext>,
+        sol_price_feed: Pubkey,
+        tolerance_bp: u64,
+        max_age: i64,
+        max_conf_bp: u64,
+    ) -> Result<()> {
+        set_sol_price_feed_ix(ctx, sol_price_feed, tolerance_bp, max_age, max_conf_bp)
+    }
+
+    pub fn init_volume_shard(ctx: Context<InitVolumeShardContext>, shard: u8) -> Result<()> {
+        init_volume_shard_ix(ctx, shard)
+    }
+
+    pub fn reset_circuit_breaker(ctx: Context<GuardianContext>, destination_domain: u32, shard: u8) -> Result<()> {
+        reset_circuit_breaker_ix(ctx, destination_domain, shard)
+    }
+
     pub fn transfer_ownership(ctx: Context<TransferOwnershipContext>, new_owner: Pubkey) -> Result<()> {
         transfer_ownership_ix(ctx, new_owner)
     }
 
-    // get fee ix
+    // denylist ixs
+
+    pub fn set_compliance(ctx: Context<SetComplianceContext>, compliance: Pubkey) -> Result<()> {
+        set_compliance_ix(ctx, compliance)
+    }
+
+    pub fn deny_sender(ctx: Context<DenySenderContext>, sender: Pubkey) -> Result<()> {
+        deny_sender_ix(ctx, sender)
+    }
+
+    pub fn allow_sender(ctx: Context<AllowSenderContext>, sender: Pubkey) -> Result<()> {
+        allow_sender_ix(ctx, sender)
+    }
+
+    pub fn deny_recipient(ctx: Context<DenyRecipientContext>, destination_domain: u32, recipient: [u8; 32]) -> Result<()> {
+        deny_recipient_ix(ctx, destination_domain, recipient)
+    }
+
+    pub fn allow_recipient(ctx: Context<AllowRecipientContext>, destination_domain: u32, recipient: [u8; 32]) -> Result<()> {
+        allow_recipient_ix(ctx, destination_domain, recipient)
+    }
+
+    // integrator ixs
+
+    pub fn set_integrator(ctx: Context<SetIntegratorContext>, authority: Pubkey, fee_token_account: Pubkey, max_fee_bp: u64) -> Result<()> {
+        set_integrator_ix(ctx, authority, fee_token_account, max_fee_bp)
+    }
+
+    pub fn remove_integrator(ctx: Context<RemoveIntegratorContext>, authority: Pubkey) -> Result<()> {
+        remove_integrator_ix(ctx, authority)
+    }
+
+    // fee split ixs
+
+    pub fn set_fee_split(ctx: Context<SetFeeSplitContext>, recipients: Vec<FeeRecipient>) -> Result<()> {
+        set_fee_split_ix(ctx, recipients)
+    }
+
+    pub fn distribute_fees<'info>(ctx: Context<'_, '_, 'info, 'info, DistributeFeesContext<'info>>) -> Result<()> {
+        distribute_fees_ix(ctx)
+    }
+
+    // fee discount ixs
+
+    pub fn set_fee_discount(ctx: Context<SetFeeDiscountContext>, user: Pubkey, discount: Discount, expires_at: i64) -> Result<()> {
+        set_fee_discount_ix(ctx, user, discount, expires_at)
+    }
 
-    pub fn get_fee(ctx: Context<GetFeeContext>, fee: u64, amount: u64) -> Result<u64> {
-        get_fee_ix(ctx, fee, amount)
+    pub fn remove_fee_discount(ctx: Context<RemoveFeeDiscountContext>, user: Pubkey) -> Result<()> {
+        remove_fee_discount_ix(ctx, user)
+    }
+
+    // quote ix
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn quote_transfer(
+        ctx: Context<QuoteTransferContext>,
+        user: Pubkey,
+        usdc_amount: u64,
+        destination_domain: u32,
+        fee: u64,
+        gas_drop_amount: u64,
+        fee_is_native: bool,
+        integrator_fee_bp: u64,
+    ) -> Result<TransferQuote> {
+        quote_transfer_ix(ctx, user, usdc_amount, destination_domain, fee, gas_drop_amount, fee_is_native, integrator_fee_bp)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn preview_transfer(
+        ctx: Context<PreviewTransferContext>,
+        usdc_amount: u64,
+        destination_domain: u32,
+        recipient: [u8; 32],
+        solana_owner: [u8; 32],
+        fee: u64,
+        fee_usd: u64,
+        deadline: u64,
+        gas_drop_amount: u64,
+        gas_drop_native_amount: u64,
+        fee_is_native: bool,
+        limit_tier: u8,
+        integrator_fee_bp: u64,
+        quote_id: [u8; 16],
+        client_memo: Option<[u8; 32]>,
+    ) -> Result<TransferPreview> {
+        preview_transfer_ix(
+            ctx,
+            usdc_amount,
+            destination_domain,
+            recipient,
+            solana_owner,
+            fee,
+            fee_usd,
+            deadline,
+            gas_drop_amount,
+            gas_drop_native_amount,
+            fee_is_native,
+            limit_tier,
+            integrator_fee_bp,
+            quote_id,
+            client_memo,
+        )
     }
 
     // transfer
 
+    #[allow(clippy::too_many_arguments)]
     pub fn transfer(
         ctx: Context<TransferContext>,
         usdc_amount: u64,
@@ -71,9 +259,15 @@ pub mod cashmere_cctp {
         recipient: [u8; 32],
         solana_owner: [u8; 32],
         fee: u64,
+        fee_usd: u64,
         deadline: u64,
         gas_drop_amount: u64,
+        gas_drop_native_amount: u64,
         fee_is_native: bool,
+        limit_tier: u8,
+        integrator_fee_bp: u64,
+        quote_id: [u8; 16],
+        client_memo: Option<[u8; 32]>,
     ) -> Result<()> {
         transfer_ix(
             ctx,
@@ -82,18 +276,110 @@ pub mod cashmere_cctp {
             recipient,
             solana_owner,
             fee,
+            fee_usd,
             deadline,
             gas_drop_amount,
+            gas_drop_native_amount,
             fee_is_native,
+            limit_tier,
+            integrator_fee_bp,
+            quote_id,
+            client_memo,
         )
     }
-}
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchTransferContext<'info>>,
+        legs: Vec<TransferLeg>,
+        fee: u64,
+        fee_usd: u64,
+        deadline: u64,
+        fee_is_native: bool,
+        limit_tier: u8,
+        quote_id: [u8; 16],
+    ) -> Result<()> {
+        batch_transfer_ix(ctx, legs, fee, fee_usd, deadline, fee_is_native, limit_tier, quote_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_large<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchTransferContext<'info>>,
+        usdc_amount: u64,
+        destination_domain: u32,
+        recipient: [u8; 32],
+        solana_owner: [u8; 32],
+        fee: u64,
+        fee_usd: u64,
+        deadline: u64,
+        fee_is_native: bool,
+        limit_tier: u8,
+        quote_id: [u8; 16],
+    ) -> Result<()> {
+        transfer_large_ix(
+            ctx,
+            usdc_amount,
+            destination_domain,
+            recipient,
+            solana_owner,
+            fee,
+            fee_usd,
+            deadline,
+            fee_is_native,
+            limit_tier,
+            quote_id,
+        )
+    }
+
+    pub fn replace_transfer(
+        ctx: Context<ReplaceTransferContext>,
+        nonce: u64,
+        original_message: Vec<u8>,
+        original_attestation: Vec<u8>,
+        new_recipient: [u8; 32],
+        new_solana_owner: [u8; 32],
+        new_destination_caller: [u8; 32],
+    ) -> Result<()> {
+        replace_transfer_ix(ctx, nonce, original_message, original_attestation, new_recipient, new_solana_owner, new_destination_caller)
+    }
+
+    pub fn reclaim_event_account(ctx: Context<ReclaimEventAccountContext>, nonce: u64, attestation: Vec<u8>) -> Result<()> {
+        reclaim_event_account_ix(ctx, nonce, attestation)
+    }
+
+    // receipt ixs
+
+    pub fn add_relayer(ctx: Context<AddRelayerContext>, authority: Pubkey) -> Result<()> {
+        add_relayer_ix(ctx, authority)
+    }
 
+    pub fn remove_relayer(ctx: Context<RemoveRelayerContext>, authority: Pubkey) -> Result<()> {
+        remove_relayer_ix(ctx, authority)
+    }
 
+    pub fn update_transfer_status(ctx: Context<UpdateTransferStatusContext>, nonce: u64, status: TransferStatus) -> Result<()> {
+        update_transfer_status_ix(ctx, nonce, status)
+    }
 
+    pub fn close_receipt(ctx: Context<CloseReceiptContext>, nonce: u64) -> Result<()> {
+        close_receipt_ix(ctx, nonce)
+    }
 
+    // gas drop ixs
 
+    pub fn init_gas_drop_escrow(ctx: Context<InitGasDropEscrowContext>, destination_domain: u32) -> Result<()> {
+        init_gas_drop_escrow_ix(ctx, destination_domain)
+    }
 
+    pub fn set_gas_price(ctx: Context<SetGasPriceContext>, domain: u32, price: u64, decimals: u8) -> Result<()> {
+        set_gas_price_ix(ctx, domain, price, decimals)
+    }
 
+    pub fn acknowledge_gas_drop(ctx: Context<AcknowledgeGasDropContext>, nonce: u64, destination_tx_hash: [u8; 32]) -> Result<()> {
+        acknowledge_gas_drop_ix(ctx, nonce, destination_tx_hash)
+    }
 
+    pub fn claim_gas_drop_refund(ctx: Context<ClaimGasDropRefundContext>, nonce: u64) -> Result<()> {
+        claim_gas_drop_refund_ix(ctx, nonce)
+    }
+}
@@ -1,6 +1,22 @@
 use anchor_lang::prelude::*;
 use bytemuck::{Pod, Zeroable};
 
+/// Number of CCTP domains covered by per-domain settings in [Config].
+pub const MAX_DOMAINS: usize = 32;
+
+/// Number of volume-based fee tiers in [Config].
+pub const FEE_TIERS: usize = 4;
+
+/// Most recipients a [FeeSplit] can pay out to.
+pub const MAX_FEE_RECIPIENTS: usize = 8;
+
+/// Number of per-user volume limit tiers a signed quote can select from.
+pub const VOLUME_LIMIT_TIERS: usize = 4;
+
+/// Number of [VolumeShard]s the global and domain volume windows and circuit breakers are split
+/// over.
+pub const VOLUME_SHARDS: usize = 8;
+
 #[derive(Debug, Copy, Clone, Pod, Zeroable)]
 #[repr(C)]
 pub struct Ed25519SignatureOffsets {
@@ -22,10 +38,252 @@ pub struct Config {
     pub gas_drop_collector_sol: Pubkey,
     pub gas_drop_collector_usdc: Pubkey,
     pub fee_bp: u64,
+    /// No longer advanced: transfers are numbered per user, by `UserState.nonce`.
     pub nonce: u64,
     pub signer_key: [u8; 32],
     pub max_usdc_gas_drop: u64, // in micro-USDC (default 100m)
-    pub max_native_gas_drop: [u64; 32],
+    pub max_native_gas_drop: [u64; MAX_DOMAINS],
+    pub volume_window: i64, // in seconds (default 1 day)
+    /// Enforced as an even share per [VolumeShard], like the domain volume and breaker limits.
+    pub global_volume_limit: u64, // in micro-USDC, 0 = unlimited
+    pub domain_volume_limit: [u64; MAX_DOMAINS],
+    pub user_volume_limit: [u64; VOLUME_LIMIT_TIERS],
+    pub guardian: Pubkey,
+    pub breaker_window: i64, // in seconds (default 1 hour)
+    pub breaker_outflow_limit: [u64; MAX_DOMAINS], // in micro-USDC, 0 = disabled
+    pub breaker_max_transfers_per_slot: u32, // 0 = disabled
+    pub min_transfer_amount: [u64; MAX_DOMAINS], // in micro-USDC
+    pub max_transfer_amount: [u64; MAX_DOMAINS], // in micro-USDC, 0 = unlimited
+    pub chain_family: [ChainFamily; MAX_DOMAINS],
+    pub compliance: Pubkey,
+    pub fee_tiers: [FeeTier; FEE_TIERS],
+    /// Incremented by every owner setter, so events can be tied to the config they ran under.
+    pub version: u64,
+    /// Time after a transfer before its undelivered gas drop can be refunded, in seconds
+    /// (default 1 day).
+    pub gas_drop_refund_timeout: i64,
+    /// Updates [GasPrice] accounts.
+    pub gas_oracle: Pubkey,
+    /// How far a gas drop payment may be from its oracle value, in bp.
+    pub gas_price_tolerance_bp: u64,
+    /// Oldest [GasPrice] accepted, in seconds. 0 = gas drop prices are not checked.
+    pub gas_price_max_age: i64,
+    /// Pyth SOL/USD `PriceUpdateV2` account native fees are checked against. Default = unchecked.
+    pub sol_price_feed: Pubkey,
+    /// How far the oracle value of a native fee may be from its signed USD value, in bp.
+    pub native_fee_tolerance_bp: u64,
+    /// Oldest SOL/USD price accepted, in seconds.
+    pub sol_price_max_age: i64,
+    /// Widest SOL/USD confidence interval accepted, in bp of the price.
+    pub sol_price_max_conf_bp: u64,
+}
+
+#[cfg(test)]
+impl Config {
+    /// Config with every field zero: no limits, no checks and every chain family unknown.
+    pub(crate) fn zeroed() -> Self {
+        Self::deserialize(&mut &[0; Config::INIT_SPACE][..]).unwrap()
+    }
+}
+
+/// Discount off `Config.fee_bp` for users whose cumulative volume reaches `min_volume`. A zero
+/// `min_volume` disables the tier.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace)]
+pub struct FeeTier {
+    pub min_volume: u64, // in micro-USDC
+    pub discount_bp: u64,
+}
+
+/// Address format of a destination domain, used to reject recipients the destination chain
+/// could never use.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum ChainFamily {
+    /// Not registered; transfers to the domain are rejected.
+    #[default]
+    Unknown,
+    /// 20-byte address, left-padded with zeros.
+    Evm,
+    /// Associated token account of `solana_owner`.
+    Solana,
+    /// 32-byte account address, other than the reserved 0x0 to 0xf.
+    Aptos,
+    /// 32-byte account address, other than the reserved 0x0 to 0xf.
+    Sui,
+    /// 20-byte bech32 payload, left-padded with zeros.
+    Noble,
+}
+
+/// Per-domain circuit breaker of a [VolumeShard]. Trips on outflow over the shard's share of
+/// `Config.breaker_outflow_limit` within `Config.breaker_window`, or more transfers in one slot
+/// than its share of `Config.breaker_max_transfers_per_slot`, and pauses the domain for the users
+/// of the shard until the guardian or owner resets it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace)]
+pub struct CircuitBreaker {
+    pub tripped: bool,
+    pub outflow: VolumeWindow,
+    pub slot: u64,
+    pub slot_transfers: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TripReason {
+    Outflow,
+    Burst,
+}
+
+/// Transfer volume over a rolling window, tracked as the current and previous fixed window. The
+/// rolling volume weights the previous window by how much of it still overlaps.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace)]
+pub struct VolumeWindow {
+    pub start: i64,
+    pub current: u64,
+    pub previous: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolumeScope {
+    Global,
+    Domain,
+    User,
+}
+
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct UserState {
+    pub bump: u8,
+    pub user: Pubkey,
+    pub volume: VolumeWindow,
+    /// All-time transfer volume, in micro-USDC. Drives the fee tiers.
+    pub cumulative_volume: u64,
+    /// Nonce of the user's last transfer. Each transfer, batch leg and split message takes the
+    /// next one, so a transfer is identified by its user and nonce.
+    pub nonce: u64,
+}
+
+impl UserState {
+    pub const SEED_PREFIX: &'static [u8] = b"user";
+}
+
+/// One slice of the global and per-domain volume windows and circuit breakers. The transfers of
+/// a user are all recorded in the shard picked by [VolumeShard::index], against an even share of
+/// each config limit, so users in different shards never write the same account while the
+/// totals across shards stay within the limits.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct VolumeShard {
+    pub bump: u8,
+    pub shard: u8,
+    pub global_volume: VolumeWindow,
+    pub domain_volume: [VolumeWindow; MAX_DOMAINS],
+    pub breakers: [CircuitBreaker; MAX_DOMAINS],
+}
+
+impl VolumeShard {
+    /// Seeds are \["volume_shard", shard\].
+    pub const SEED_PREFIX: &'static [u8] = b"volume_shard";
+
+    /// Shard the transfers of `user` are recorded in.
+    pub fn index(user: &Pubkey) -> u8 {
+        (user.to_bytes()[0] as usize % VOLUME_SHARDS) as u8
+    }
+
+    /// Part of a config limit each shard enforces. Zero stays unlimited, and a non-zero limit
+    /// never rounds down to zero.
+    pub fn share(limit: u64) -> u64 {
+        match limit {
+            0 => 0,
+            limit => (limit / VOLUME_SHARDS as u64).max(1),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum Discount {
+    /// Flat rate; `Config.fee_bp` still applies if it is lower.
+    FeeBp { fee_bp: u64 },
+    /// Takes `discount_bp` off `Config.fee_bp`.
+    Percentage { discount_bp: u64 },
+}
+
+/// Negotiated rate for one user. Applies until `expires_at`, or indefinitely if it is zero. When
+/// the user also qualifies for a fee tier, the lower rate wins.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct FeeDiscount {
+    pub bump: u8,
+    pub user: Pubkey,
+    pub discount: Discount,
+    pub expires_at: i64,
+}
+
+impl FeeDiscount {
+    pub const SEED_PREFIX: &'static [u8] = b"fee_discount";
+}
+
+/// Marks a sender or destination recipient as blocked. Transfers involving it fail while the
+/// entry exists.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct DenylistEntry {
+    pub bump: u8,
+    pub added_at: i64,
+}
+
+impl DenylistEntry {
+    /// Seeds are \["denied_sender", sender\].
+    pub const SENDER_SEED_PREFIX: &'static [u8] = b"denied_sender";
+    /// Seeds are \["denied_recipient", destination domain (LE), recipient\].
+    pub const RECIPIENT_SEED_PREFIX: &'static [u8] = b"denied_recipient";
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DenylistSubject {
+    Sender { sender: Pubkey },
+    Recipient { destination_domain: u32, recipient: [u8; 32] },
+}
+
+/// Wallet or dApp registered to take a share of the transfers it routes. The share is charged on
+/// top of the Cashmere fee, at a rate chosen per transfer up to `max_fee_bp`.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Integrator {
+    pub bump: u8,
+    pub authority: Pubkey,
+    /// USDC token account receiving the integrator fee.
+    pub fee_token_account: Pubkey,
+    pub max_fee_bp: u64,
+}
+
+impl Integrator {
+    pub const SEED_PREFIX: &'static [u8] = b"integrator";
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct FeeRecipient {
+    /// Receives the SOL share.
+    pub wallet: Pubkey,
+    /// USDC token account receiving the USDC share.
+    pub token_account: Pubkey,
+    /// Share of the accrued fees, in bp. Weights of a split add up to 10000.
+    pub weight_bp: u16,
+}
+
+/// Splits the fees accrued in the fee vaults between several recipients. While a split is set,
+/// the config fee collectors point at the vaults and `distribute_fees` pays them out.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct FeeSplit {
+    pub bump: u8,
+    pub vault_bump: u8,
+    #[max_len(MAX_FEE_RECIPIENTS)]
+    pub recipients: Vec<FeeRecipient>,
+}
+
+impl FeeSplit {
+    pub const SEED_PREFIX: &'static [u8] = b"fee_split";
+    /// Seeds of the SOL fee vault, which is also the authority of the USDC fee vault.
+    pub const VAULT_SEED_PREFIX: &'static [u8] = b"fee_vault";
+    pub const USDC_VAULT_SEED_PREFIX: &'static [u8] = b"fee_vault_usdc";
 }
 
 #[account]
@@ -37,3 +295,190 @@ pub struct Custodian {
 impl Custodian {
     pub const SEED_PREFIX: &'static [u8] = b"emitter";
 }
+
+/// Record of a transfer, seeded by its user and the nonce emitted in `TransferEvent`. Optional: it
+/// lets the program authorize follow-up actions on the CCTP message it created, and relayers
+/// report delivery on it. Closed by the user once the transfer is final.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct TransferReceipt {
+    pub bump: u8,
+    pub nonce: u64,
+    pub user: Pubkey,
+    pub cctp_nonce: u64,
+    /// Current CCTP message account. Updated when the message is replaced.
+    pub cctp_message: Pubkey,
+    /// Receives the rent of the CCTP message account once it is reclaimed.
+    pub rent_payer: Pubkey,
+    /// Burned amount, in micro-USDC.
+    pub amount: u64,
+    pub destination_domain: u32,
+    pub recipient: [u8; 32],
+    /// Payment for the gas drop, held in the domain's [GasDropEscrow] until claimed or refunded.
+    /// In lamports if `fee_is_native`, otherwise in micro-USDC.
+    pub gas_drop_amount: u64,
+    /// Gas drop owed on the destination chain, in the smallest unit of its native token.
+    pub gas_drop_native_amount: u64,
+    pub fee_is_native: bool,
+    pub status: TransferStatus,
+    /// Relayer that reported the mint, the only one that can acknowledge the gas drop.
+    pub relayer: Pubkey,
+    /// Destination transaction that delivered the gas drop, set on acknowledgement.
+    pub gas_drop_tx_hash: [u8; 32],
+    /// Set once the CCTP message account has been reclaimed.
+    pub message_reclaimed: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl TransferReceipt {
+    /// Seeds are \["receipt", user, nonce (LE)\].
+    pub const SEED_PREFIX: &'static [u8] = b"receipt";
+
+    /// Nothing is left to happen to the transfer: the USDC is minted and the gas drop, if any,
+    /// delivered, or the transfer was refunded.
+    pub fn is_final(&self) -> bool {
+        match self.status {
+            TransferStatus::Burned => false,
+            TransferStatus::Minted => self.gas_drop_amount == 0,
+            TransferStatus::GasDelivered | TransferStatus::Refunded => true,
+        }
+    }
+}
+
+/// Progress of a transfer with a receipt. Only moves forward.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, InitSpace)]
+pub enum TransferStatus {
+    /// USDC burned on Solana.
+    Burned,
+    /// USDC minted on the destination chain.
+    Minted,
+    /// Gas drop delivered on the destination chain.
+    GasDelivered,
+    /// Gas drop refunded to the user after going undelivered for
+    /// `Config.gas_drop_refund_timeout`.
+    Refunded,
+}
+
+/// Program-owned escrow of the gas drops paid for one destination domain. Holds SOL gas drops
+/// itself and owns the USDC escrow token account. Payments stay here until a relayer claims them
+/// by acknowledging delivery, or the user is refunded.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct GasDropEscrow {
+    pub bump: u8,
+    pub destination_domain: u32,
+    /// Unsettled SOL gas drops, in lamports.
+    pub outstanding_native: u64,
+    /// Unsettled USDC gas drops, in micro-USDC.
+    pub outstanding_usdc: u64,
+}
+
+impl GasDropEscrow {
+    /// Seeds are \["gas_drop_escrow", destination domain (LE)\].
+    pub const SEED_PREFIX: &'static [u8] = b"gas_drop_escrow";
+    /// Seeds are \["gas_drop_escrow_usdc", destination domain (LE)\].
+    pub const USDC_SEED_PREFIX: &'static [u8] = b"gas_drop_escrow_usdc";
+
+    pub fn outstanding_mut(&mut self, fee_is_native: bool) -> &mut u64 {
+        if fee_is_native {
+            &mut self.outstanding_native
+        } else {
+            &mut self.outstanding_usdc
+        }
+    }
+}
+
+/// Price of the native token of a domain, posted by `Config.gas_oracle`. The Solana domain's
+/// price converts SOL gas drop payments.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct GasPrice {
+    pub bump: u8,
+    pub domain: u32,
+    /// Price of one whole native token, in micro-USDC.
+    pub price: u64,
+    /// Decimals of the native token's smallest unit (18 for wei, 9 for lamports).
+    pub decimals: u8,
+    pub updated_at: i64,
+}
+
+impl GasPrice {
+    /// Seeds are \["gas_price", domain (LE)\].
+    pub const SEED_PREFIX: &'static [u8] = b"gas_price";
+}
+
+/// Relayer allowed to report transfer status on receipts, and to claim the gas drops it
+/// delivers.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Relayer {
+    pub bump: u8,
+    pub authority: Pubkey,
+}
+
+impl Relayer {
+    pub const SEED_PREFIX: &'static [u8] = b"relayer";
+}
+
+/// Fee breakdown of a transfer, returned by `quote_transfer`. USDC amounts are in micro-USDC and
+/// native amounts in lamports.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TransferQuote {
+    pub usdc_amount: u64,
+    pub destination_domain: u32,
+    pub fee_is_native: bool,
+    /// `Config.fee_bp`, before discounts.
+    pub base_fee_bp: u64,
+    /// Percentage fee rate applied, after discounts.
+    pub fee_bp: u64,
+    pub usdc_percentage_fee: u64,
+    pub usdc_static_fee: u64,
+    pub native_fee: u64,
+    /// Paid in lamports if `fee_is_native`, otherwise in micro-USDC.
+    pub gas_drop_amount: u64,
+    /// Gas drop cap applied to this transfer. Zero means uncapped.
+    pub gas_drop_cap: u64,
+    /// Smallest `usdc_amount` accepted for the destination domain.
+    pub min_amount: u64,
+    /// Largest `usdc_amount` accepted for the destination domain. Zero means unlimited.
+    pub max_amount: u64,
+    pub integrator_fee_bp: u64,
+    /// Part of `usdc_fee` paid to the integrator.
+    pub integrator_fee: u64,
+    /// Total USDC fee deducted from `usdc_amount`.
+    pub usdc_fee: u64,
+    pub net_burn_amount: u64,
+    /// USDC leaving the user's token account, including a USDC gas drop.
+    pub total_usdc_cost: u64,
+    /// Lamports leaving the user's wallet, excluding rent.
+    pub total_native_cost: u64,
+    /// True if a fee or cost does not fit in a `u64`. The amounts that overflowed read `u64::MAX`.
+    pub overflow: bool,
+    pub rejected: bool,
+    /// Error code `transfer` would fail with, zero if not rejected.
+    pub rejection_code: u32,
+}
+
+/// Result of `preview_transfer`. Each `*_error` field holds the error code the corresponding
+/// `transfer` check would fail with, or zero if it passes.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TransferPreview {
+    pub quote: TransferQuote,
+    pub signature_error: u32,
+    pub deadline_error: u32,
+    pub fee_error: u32,
+    pub balance_error: u32,
+    pub cctp_accounts_error: u32,
+    pub volume_error: u32,
+    pub circuit_breaker_error: u32,
+    pub burn_limit_error: u32,
+    pub recipient_error: u32,
+    pub denylist_error: u32,
+    pub integrator_error: u32,
+    pub gas_drop_error: u32,
+    pub gas_price_error: u32,
+    pub native_fee_error: u32,
+    /// True if every check passes.
+    pub valid: bool,
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::TransferError,
+    events::CircuitBreakerTripped,
+    state::{
+        CircuitBreaker,
+        Config,
+        TripReason,
+        VolumeShard,
+    },
+};
+
+impl CircuitBreaker {
+    pub fn check(&self) -> Result<()> {
+        require!(!self.tripped, TransferError::DomainPaused);
+        Ok(())
+    }
+
+    /// Records one transfer of `amount` and trips the breaker if it pushes outflow or the
+    /// transfers in this slot over their limits. Returns why it tripped, if it did.
+    pub fn record(
+        &mut self,
+        now: i64,
+        slot: u64,
+        window: i64,
+        outflow_limit: u64,
+        max_transfers_per_slot: u32,
+        amount: u64,
+    ) -> Option<TripReason> {
+        self.outflow.record(now, window, amount);
+        if self.slot != slot {
+            self.slot = slot;
+            self.slot_transfers = 0;
+        }
+        self.slot_transfers += 1;
+
+        let reason = if outflow_limit > 0 && self.outflow.volume(now, window) > outflow_limit {
+            Some(TripReason::Outflow)
+        } else if max_transfers_per_slot > 0 && self.slot_transfers > max_transfers_per_slot {
+            Some(TripReason::Burst)
+        } else {
+            None
+        };
+        self.tripped |= reason.is_some();
+        reason
+    }
+}
+
+/// Fails if `destination_domain` is paused for the users of `shard`.
+pub fn check_circuit_breaker(shard: &VolumeShard, destination_domain: u32) -> Result<()> {
+    shard.breakers
+        .get(destination_domain as usize)
+        .ok_or(TransferError::InvalidDestinationDomain)?
+        .check()
+}
+
+/// Checks the breaker of `destination_domain` in `shard` and records a transfer against it, held
+/// to the shard's share of the limits. The transfer that trips the breaker still goes through, as
+/// failing it would roll the trip back; every transfer after it fails until the breaker is reset.
+pub fn record_outflow(
+    config: &Config,
+    shard: &mut VolumeShard,
+    destination_domain: u32,
+    amount: u64,
+    now: i64,
+    slot: u64,
+) -> Result<Option<CircuitBreakerTripped>> {
+    check_circuit_breaker(shard, destination_domain)?;
+
+    let domain = destination_domain as usize;
+    let (window, outflow_limit, max_transfers_per_slot) = (
+        config.breaker_window,
+        VolumeShard::share(config.breaker_outflow_limit[domain]),
+        VolumeShard::share(config.breaker_max_transfers_per_slot as u64) as u32,
+    );
+    let breaker = &mut shard.breakers[domain];
+    Ok(breaker
+        .record(now, slot, window, outflow_limit, max_transfers_per_slot, amount)
+        .map(|reason| CircuitBreakerTripped {
+            destination_domain,
+            shard: shard.shard,
+            reason,
+            outflow: breaker.outflow.volume(now, window),
+            slot_transfers: breaker.slot_transfers,
+            slot,
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        state::VOLUME_SHARDS,
+        utils::error::assert_error,
+    };
+
+    #[test]
+    fn trips_on_outflow() {
+        let mut breaker = CircuitBreaker::default();
+        assert_eq!(breaker.record(1_000, 1, 3_600, 100, 0, 60), None);
+        assert!(breaker.check().is_ok());
+        assert_eq!(breaker.record(1_001, 2, 3_600, 100, 0, 41), Some(TripReason::Outflow));
+        assert_error(breaker.check(), TransferError::DomainPaused);
+    }
+
+    #[test]
+    fn trips_on_burst_within_a_slot() {
+        let mut breaker = CircuitBreaker::default();
+        assert_eq!(breaker.record(1_000, 1, 3_600, 0, 2, 1), None);
+        assert_eq!(breaker.record(1_000, 1, 3_600, 0, 2, 1), None);
+        assert_eq!(breaker.record(1_000, 2, 3_600, 0, 2, 1), None);
+        assert_eq!(breaker.record(1_000, 2, 3_600, 0, 2, 1), None);
+        assert_eq!(breaker.record(1_000, 2, 3_600, 0, 2, 1), Some(TripReason::Burst));
+        assert!(breaker.tripped);
+    }
+
+    #[test]
+    fn zero_length_window_never_trips_on_outflow() {
+        let mut breaker = CircuitBreaker::default();
+        assert_eq!(breaker.record(1_000, 1, 0, 100, 0, u64::MAX), None);
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn trip_is_recorded_in_shard() {
+        let mut config = Config::zeroed();
+        config.breaker_window = 3_600;
+        config.breaker_outflow_limit[6] = 100 * VOLUME_SHARDS as u64;
+        let mut shard = VolumeShard { shard: 3, ..VolumeShard::default() };
+        let tripped = record_outflow(&config, &mut shard, 6, 101, 1_000, 1).unwrap().unwrap();
+        assert_eq!(
+            (tripped.destination_domain, tripped.shard, tripped.reason, tripped.outflow),
+            (6, 3, TripReason::Outflow, 101),
+        );
+        assert_error(check_circuit_breaker(&shard, 6), TransferError::DomainPaused);
+        assert!(check_circuit_breaker(&shard, 0).is_ok());
+        assert!(check_circuit_breaker(&VolumeShard::default(), 6).is_ok());
+        assert_error(check_circuit_breaker(&shard, 32), TransferError::InvalidDestinationDomain);
+    }
+}
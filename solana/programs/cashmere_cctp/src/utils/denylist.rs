@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ComplianceError,
+    state::DenylistEntry,
+};
+
+pub fn denied_recipient_address(destination_domain: u32, recipient: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[DenylistEntry::RECIPIENT_SEED_PREFIX, &destination_domain.to_le_bytes(), recipient],
+        &crate::ID,
+    ).0
+}
+
+/// Fails if `entry`, the denylist PDA of `sender`, exists.
+pub fn check_sender_allowed(entry: &AccountInfo) -> Result<()> {
+    require!(entry.data_is_empty(), ComplianceError::SenderDenied);
+    Ok(())
+}
+
+/// Fails if `entry`, the denylist PDA of a recipient, exists. Its address is checked by the seeds
+/// constraint of the account.
+pub fn check_recipient_allowed(entry: &AccountInfo) -> Result<()> {
+    require!(entry.data_is_empty(), ComplianceError::RecipientDenied);
+    Ok(())
+}
+
+/// Fails if `entry` is not the denylist PDA of `recipient` on `destination_domain`, or it exists.
+/// For remaining accounts, which have no seeds constraint. The entry must not exist, so there is no
+/// stored bump to check against, and a bump supplied by the caller could be a non-canonical one
+/// addressing an empty account.
+pub fn check_unconstrained_recipient_allowed(entry: &AccountInfo, destination_domain: u32, recipient: &[u8; 32]) -> Result<()> {
+    require_keys_eq!(
+        entry.key(),
+        denied_recipient_address(destination_domain, recipient),
+        ComplianceError::InvalidDenylistEntry
+    );
+    check_recipient_allowed(entry)
+}
@@ -0,0 +1,18 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::program_error::ProgramError,
+};
+
+/// Error code a failed check would abort the transaction with, or zero if it passed.
+pub fn error_code(result: Result<()>) -> u32 {
+    match result {
+        Ok(()) => 0,
+        Err(err) => u64::from(ProgramError::from(err)) as u32,
+    }
+}
+
+/// Asserts that `result` failed with `error`.
+#[cfg(test)]
+pub(crate) fn assert_error(result: Result<()>, error: impl Into<Error>) {
+    assert_eq!(error_code(result), error_code(Err(error.into())));
+}
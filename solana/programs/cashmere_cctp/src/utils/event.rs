@@ -0,0 +1,21 @@
+/// Emits `$event` with `emit_cpi!`, so it is kept in the transaction's inner instructions rather
+/// than its logs. With the `event-logs` feature, it is logged with `emit!` instead; the event
+/// authority is still required so both builds take the same accounts. `$ctx` is the
+/// instruction's `Context`, whose accounts must be `#[event_cpi]`.
+macro_rules! emit_event {
+    ($ctx:expr, $event:expr) => {{
+        let event = $event;
+        #[cfg(not(feature = "event-logs"))]
+        {
+            let ctx = &$ctx;
+            anchor_lang::prelude::emit_cpi!(event);
+        }
+        #[cfg(feature = "event-logs")]
+        {
+            let _ = &$ctx;
+            anchor_lang::prelude::emit!(event);
+        }
+    }};
+}
+
+pub(crate) use emit_event;
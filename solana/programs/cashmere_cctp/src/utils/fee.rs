@@ -1,5 +1,171 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::TransferError,
+    state::{
+        Config,
+        Discount,
+        FeeDiscount,
+        TransferQuote,
+    },
+    utils::error_code,
+};
+
 const BP: u64 = 10000;
 
-pub fn calculate_fee(amount: u64, fee_bp: u64, fee_static: u64) -> u64 {
-    (fee_bp * amount) / BP + fee_static
+/// `fee_bp` of `amount` plus `fee_static`, or `None` if it does not fit in a `u64`.
+pub fn calculate_fee(amount: u64, fee_bp: u64, fee_static: u64) -> Option<u64> {
+    let percentage_fee = fee_bp as u128 * amount as u128 / BP as u128;
+    u64::try_from(percentage_fee).ok()?.checked_add(fee_static)
+}
+
+/// Percentage fee rate for a user: the lowest of `Config.fee_bp`, their unexpired negotiated
+/// discount and the best fee tier their cumulative volume reaches.
+pub fn effective_fee_bp(config: &Config, discount: Option<&FeeDiscount>, cumulative_volume: u64, now: i64) -> u64 {
+    let discounted = |discount_bp: u64| config.fee_bp * BP.saturating_sub(discount_bp) / BP;
+    let negotiated = discount
+        .filter(|discount| discount.expires_at == 0 || now < discount.expires_at)
+        .map(|discount| match discount.discount {
+            Discount::FeeBp { fee_bp } => fee_bp,
+            Discount::Percentage { discount_bp } => discounted(discount_bp),
+        })
+        .unwrap_or(config.fee_bp);
+    let tiered = config.fee_tiers
+        .iter()
+        .filter(|tier| tier.min_volume > 0 && cumulative_volume >= tier.min_volume)
+        .map(|tier| discounted(tier.discount_bp))
+        .min()
+        .unwrap_or(config.fee_bp);
+    config.fee_bp.min(negotiated).min(tiered)
+}
+
+/// Prices a transfer against the current config, at the percentage fee rate `fee_bp` (see
+/// [effective_fee_bp]). This is the single source of truth for what `transfer_ix` charges;
+/// `quote_transfer_ix` returns the same value to clients.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_transfer(
+    config: &Config,
+    fee_bp: u64,
+    usdc_amount: u64,
+    destination_domain: u32,
+    fee: u64,
+    gas_drop_amount: u64,
+    fee_is_native: bool,
+    integrator_fee_bp: u64,
+) -> TransferQuote {
+    let usdc_percentage_fee = calculate_fee(usdc_amount, fee_bp, 0);
+    let integrator_fee = calculate_fee(usdc_amount, integrator_fee_bp, 0);
+    let (usdc_static_fee, native_fee) = if fee_is_native { (0, fee) } else { (fee, 0) };
+    let usdc_fee = usdc_percentage_fee
+        .zip(integrator_fee)
+        .and_then(|(percentage_fee, integrator_fee)| percentage_fee.checked_add(integrator_fee)?.checked_add(usdc_static_fee));
+    let domain_setting = |settings: &[u64]| settings.get(destination_domain as usize).copied().unwrap_or(0);
+    let gas_drop_cap = if fee_is_native {
+        domain_setting(&config.max_native_gas_drop)
+    } else {
+        config.max_usdc_gas_drop
+    };
+    let (usdc_gas_drop, native_gas_drop) = if fee_is_native { (0, gas_drop_amount) } else { (gas_drop_amount, 0) };
+    let total_usdc_cost = usdc_amount.checked_add(usdc_gas_drop);
+    let total_native_cost = native_fee.checked_add(native_gas_drop);
+    let overflow = usdc_fee.is_none() || total_usdc_cost.is_none() || total_native_cost.is_none();
+    let usdc_fee = usdc_fee.unwrap_or(u64::MAX);
+
+    let mut quote = TransferQuote {
+        usdc_amount,
+        destination_domain,
+        fee_is_native,
+        base_fee_bp: config.fee_bp,
+        fee_bp,
+        usdc_percentage_fee: usdc_percentage_fee.unwrap_or(u64::MAX),
+        usdc_static_fee,
+        native_fee,
+        gas_drop_amount,
+        gas_drop_cap,
+        min_amount: domain_setting(&config.min_transfer_amount),
+        max_amount: domain_setting(&config.max_transfer_amount),
+        integrator_fee_bp,
+        integrator_fee: integrator_fee.unwrap_or(u64::MAX),
+        usdc_fee,
+        net_burn_amount: usdc_amount.saturating_sub(usdc_fee),
+        total_usdc_cost: total_usdc_cost.unwrap_or(u64::MAX),
+        total_native_cost: total_native_cost.unwrap_or(u64::MAX),
+        overflow,
+        rejected: false,
+        rejection_code: 0,
+    };
+    quote.rejection_code = error_code(quote.validate());
+    quote.rejected = quote.rejection_code != 0;
+    quote
+}
+
+impl TransferQuote {
+    /// Fails with the error `transfer_ix` would abort with for this quote.
+    pub fn validate(&self) -> Result<()> {
+        require!(!self.overflow, TransferError::AmountOverflow);
+        require!(
+            !self.fee_is_native || (self.destination_domain as usize) < crate::state::MAX_DOMAINS,
+            TransferError::InvalidDestinationDomain
+        );
+        require!(self.usdc_amount >= self.min_amount, TransferError::AmountBelowMinimum);
+        require!(self.max_amount == 0 || self.usdc_amount <= self.max_amount, TransferError::AmountAboveMaximum);
+        require!(self.usdc_amount >= self.usdc_fee, TransferError::FeeExceedsAmount);
+        require!(self.net_burn_amount > 0, TransferError::ZeroBurnAmount);
+        require!(self.gas_drop_cap == 0 || self.gas_drop_amount <= self.gas_drop_cap, TransferError::GasDropLimitExceeded);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::error::assert_error;
+
+    fn quote(config: &Config, usdc_amount: u64, fee: u64, gas_drop_amount: u64, fee_is_native: bool) -> TransferQuote {
+        quote_transfer(config, 0, usdc_amount, 0, fee, gas_drop_amount, fee_is_native, 0)
+    }
+
+    #[test]
+    fn calculate_fee_does_not_overflow() {
+        assert_eq!(calculate_fee(1_000_000, 25, 10), Some(2_510));
+        assert_eq!(calculate_fee(u64::MAX, 10000, 0), Some(u64::MAX));
+        assert_eq!(calculate_fee(u64::MAX, 10000, 1), None);
+        assert_eq!(calculate_fee(u64::MAX, 20000, 0), None);
+    }
+
+    #[test]
+    fn fee_equal_to_amount_leaves_nothing_to_burn() {
+        let quote = quote(&Config::zeroed(), 1_000_000, 1_000_000, 0, false);
+        assert_eq!(quote.net_burn_amount, 0);
+        assert_error(quote.validate(), TransferError::ZeroBurnAmount);
+    }
+
+    #[test]
+    fn fee_above_amount_is_rejected() {
+        let quote = quote(&Config::zeroed(), 1_000_000, 1_000_001, 0, false);
+        assert_error(quote.validate(), TransferError::FeeExceedsAmount);
+    }
+
+    #[test]
+    fn overflowing_fee_is_rejected() {
+        let config = Config::zeroed();
+        let quote = quote_transfer(&config, 100, 1_000_000, 0, u64::MAX, 0, false, 0);
+        assert!(quote.overflow && quote.rejected);
+        assert_error(quote.validate(), TransferError::AmountOverflow);
+    }
+
+    #[test]
+    fn usdc_gas_drop_at_cap_is_accepted() {
+        let mut config = Config::zeroed();
+        config.max_usdc_gas_drop = 5_000_000;
+        assert!(quote(&config, 1_000_000, 0, 5_000_000, false).validate().is_ok());
+        assert_error(quote(&config, 1_000_000, 0, 5_000_001, false).validate(), TransferError::GasDropLimitExceeded);
+    }
+
+    #[test]
+    fn native_gas_drop_at_cap_is_accepted() {
+        let mut config = Config::zeroed();
+        config.max_native_gas_drop[0] = 50_000_000;
+        assert!(quote(&config, 1_000_000, 0, 50_000_000, true).validate().is_ok());
+        assert_error(quote(&config, 1_000_000, 0, 50_000_001, true).validate(), TransferError::GasDropLimitExceeded);
+    }
 }
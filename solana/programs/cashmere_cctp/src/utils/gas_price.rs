@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::GasDropError,
+    state::{
+        Config,
+        GasPrice,
+    },
+};
+
+/// Most decimals a [GasPrice] can have.
+pub const MAX_GAS_PRICE_DECIMALS: u8 = 18;
+
+impl GasPrice {
+    /// Value of `amount` smallest units of the native token, in micro-USDC.
+    pub fn to_usdc(&self, amount: u64) -> u128 {
+        amount as u128 * self.price as u128 / 10u128.pow(self.decimals as u32)
+    }
+
+    /// Smallest units of the native token worth `usdc_amount` micro-USDC.
+    pub fn from_usdc(&self, usdc_amount: u128) -> u128 {
+        usdc_amount.saturating_mul(10u128.pow(self.decimals as u32)) / self.price as u128
+    }
+
+    fn check(&self, now: i64, max_age: i64) -> Result<()> {
+        require!(self.price > 0, GasDropError::InvalidGasPrice);
+        require!(now.saturating_sub(self.updated_at) <= max_age, GasDropError::StaleGasPrice);
+        Ok(())
+    }
+}
+
+/// Fails unless `gas_drop_amount` is within `Config.gas_price_tolerance_bp` of what
+/// `gas_drop_native_amount` is worth at the destination gas price, converted to lamports at the
+/// Solana gas price if `fee_is_native`. Skipped without a gas drop or while
+/// `Config.gas_price_max_age` is zero.
+pub fn check_gas_drop_price(
+    config: &Config,
+    gas_price: Option<&GasPrice>,
+    native_gas_price: Option<&GasPrice>,
+    gas_drop_amount: u64,
+    gas_drop_native_amount: u64,
+    fee_is_native: bool,
+    now: i64,
+) -> Result<()> {
+    if gas_drop_amount == 0 || config.gas_price_max_age == 0 {
+        return Ok(());
+    }
+    let gas_price = gas_price.ok_or(GasDropError::MissingGasPrice)?;
+    gas_price.check(now, config.gas_price_max_age)?;
+
+    let mut expected = gas_price.to_usdc(gas_drop_native_amount);
+    if fee_is_native {
+        let native_gas_price = native_gas_price.ok_or(GasDropError::MissingGasPrice)?;
+        native_gas_price.check(now, config.gas_price_max_age)?;
+        expected = native_gas_price.from_usdc(expected);
+    }
+
+    let tolerance = expected * config.gas_price_tolerance_bp as u128 / 10000;
+    require!(
+        (gas_drop_amount as u128).abs_diff(expected) <= tolerance,
+        GasDropError::GasDropPriceMismatch
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::error::assert_error;
+
+    const NOW: i64 = 1_000_000;
+
+    fn gas_price(price: u64, decimals: u8) -> GasPrice {
+        GasPrice { bump: 0, domain: 0, price, decimals, updated_at: NOW }
+    }
+
+    /// ETH at $3,000.
+    fn eth() -> GasPrice {
+        gas_price(3_000_000_000, 18)
+    }
+
+    /// SOL at $150.
+    fn sol() -> GasPrice {
+        gas_price(150_000_000, 9)
+    }
+
+    fn config() -> Config {
+        let mut config = Config::zeroed();
+        config.gas_price_max_age = 60;
+        config.gas_price_tolerance_bp = 100;
+        config
+    }
+
+    #[test]
+    fn converts_between_native_units_and_usdc() {
+        assert_eq!(eth().to_usdc(10_000_000_000_000_000), 30_000_000);
+        assert_eq!(sol().from_usdc(30_000_000), 200_000_000);
+        assert_eq!(sol().to_usdc(200_000_000), 30_000_000);
+        assert_eq!(gas_price(u64::MAX, 0).from_usdc(u128::MAX), u128::MAX / u64::MAX as u128);
+    }
+
+    #[test]
+    fn usdc_gas_drop_within_tolerance() {
+        let config = config();
+        let check = |amount| check_gas_drop_price(&config, Some(&eth()), None, amount, 10_000_000_000_000_000, false, NOW);
+        assert!(check(30_000_000).is_ok());
+        assert!(check(30_300_000).is_ok());
+        assert!(check(29_700_000).is_ok());
+        assert_error(check(30_300_001), GasDropError::GasDropPriceMismatch);
+        assert_error(check(29_699_999), GasDropError::GasDropPriceMismatch);
+    }
+
+    #[test]
+    fn native_gas_drop_converts_through_sol() {
+        let config = config();
+        let check = |amount, native: Option<&GasPrice>| {
+            check_gas_drop_price(&config, Some(&eth()), native, amount, 10_000_000_000_000_000, true, NOW)
+        };
+        assert!(check(200_000_000, Some(&sol())).is_ok());
+        assert_error(check(210_000_000, Some(&sol())), GasDropError::GasDropPriceMismatch);
+        assert_error(check(200_000_000, None), GasDropError::MissingGasPrice);
+    }
+
+    #[test]
+    fn stale_missing_or_zero_price_is_rejected() {
+        let config = config();
+        let mut stale = eth();
+        stale.updated_at = NOW - 61;
+        assert_error(check_gas_drop_price(&config, Some(&stale), None, 1, 1, false, NOW), GasDropError::StaleGasPrice);
+        assert_error(check_gas_drop_price(&config, None, None, 1, 1, false, NOW), GasDropError::MissingGasPrice);
+        let zero = gas_price(0, 18);
+        assert_error(check_gas_drop_price(&config, Some(&zero), None, 1, 1, false, NOW), GasDropError::InvalidGasPrice);
+    }
+
+    #[test]
+    fn skipped_without_gas_drop_or_max_age() {
+        assert!(check_gas_drop_price(&config(), None, None, 0, 1, false, NOW).is_ok());
+        assert!(check_gas_drop_price(&Config::zeroed(), None, None, 1, 1, false, NOW).is_ok());
+    }
+}
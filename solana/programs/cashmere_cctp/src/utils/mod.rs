@@ -1,5 +1,38 @@
+pub mod circuit_breaker;
+pub mod denylist;
 pub mod ed25519;
+pub mod error;
+pub mod event;
 pub mod fee;
+pub mod gas_price;
+pub mod native_fee;
+pub mod rate_limit;
+pub mod recipient;
 
+pub use circuit_breaker::{
+    check_circuit_breaker,
+    record_outflow,
+};
+pub use denylist::{
+    check_recipient_allowed,
+    check_sender_allowed,
+    check_unconstrained_recipient_allowed,
+};
 pub use ed25519::verify_ed25519_ix;
-pub use fee::calculate_fee;
+pub use error::error_code;
+pub(crate) use event::emit_event;
+pub use fee::{
+    calculate_fee,
+    effective_fee_bp,
+    quote_transfer,
+};
+pub use gas_price::{
+    check_gas_drop_price,
+    MAX_GAS_PRICE_DECIMALS,
+};
+pub use native_fee::check_native_fee;
+pub use rate_limit::{
+    check_volume_limits,
+    record_volume,
+};
+pub use recipient::validate_recipient;
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::PriceFeedError,
+    pyth::{
+        PriceUpdateV2,
+        VerificationLevel,
+    },
+    state::Config,
+};
+
+/// Lamports per SOL, as a power of ten.
+const SOL_DECIMALS: i32 = 9;
+/// Micro-USD per USD, as a power of ten.
+const USD_DECIMALS: i32 = 6;
+
+/// Value of `lamports` at the price of `price_update`, in micro-USD.
+fn lamports_to_usd(price_update: &PriceUpdateV2, lamports: u64) -> u128 {
+    let message = &price_update.price_message;
+    let value = lamports as u128 * message.price as u128;
+    let exponent = message.exponent + USD_DECIMALS - SOL_DECIMALS;
+    if exponent >= 0 {
+        value.saturating_mul(10u128.saturating_pow(exponent as u32))
+    } else {
+        match 10u128.checked_pow(exponent.unsigned_abs()) {
+            Some(divisor) => value / divisor,
+            None => 0,
+        }
+    }
+}
+
+/// Fails unless a native `fee` is within `Config.native_fee_tolerance_bp` of `fee_usd`, the
+/// micro-USD value the backend signed it at, when valued at a fresh and confident SOL/USD price.
+/// Skipped for USDC fees and while `Config.sol_price_feed` is unset.
+pub fn check_native_fee(
+    config: &Config,
+    price_update: Option<&PriceUpdateV2>,
+    fee: u64,
+    fee_usd: u64,
+    fee_is_native: bool,
+    now: i64,
+) -> Result<()> {
+    if !fee_is_native || fee == 0 || config.sol_price_feed == Pubkey::default() {
+        return Ok(());
+    }
+    let price_update = price_update.ok_or(PriceFeedError::MissingPriceFeed)?;
+    require!(
+        price_update.verification_level == VerificationLevel::Full,
+        PriceFeedError::UnverifiedPrice
+    );
+    let message = &price_update.price_message;
+    require!(message.price > 0, PriceFeedError::InvalidPrice);
+    require!(
+        now.saturating_sub(message.publish_time) <= config.sol_price_max_age,
+        PriceFeedError::StalePrice
+    );
+    require!(
+        message.conf as u128 * 10000 <= message.price as u128 * config.sol_price_max_conf_bp as u128,
+        PriceFeedError::PriceUncertain
+    );
+
+    let tolerance = fee_usd as u128 * config.native_fee_tolerance_bp as u128 / 10000;
+    require!(
+        lamports_to_usd(price_update, fee).abs_diff(fee_usd as u128) <= tolerance,
+        PriceFeedError::NativeFeeMismatch
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pyth::PriceFeedMessage,
+        utils::error::assert_error,
+    };
+
+    const NOW: i64 = 1_000_000;
+
+    /// SOL at $150.00 with a $0.15 confidence interval, as Pyth publishes it.
+    fn price_update() -> PriceUpdateV2 {
+        PriceUpdateV2 {
+            write_authority: Pubkey::default(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id: [0; 32],
+                price: 15_000_000_000,
+                conf: 15_000_000,
+                exponent: -8,
+                publish_time: NOW,
+                prev_publish_time: NOW - 1,
+                ema_price: 15_000_000_000,
+                ema_conf: 15_000_000,
+            },
+            posted_slot: 0,
+        }
+    }
+
+    fn config() -> Config {
+        let mut config = Config::zeroed();
+        config.sol_price_feed = Pubkey::new_unique();
+        config.native_fee_tolerance_bp = 100;
+        config.sol_price_max_age = 60;
+        config.sol_price_max_conf_bp = 50;
+        config
+    }
+
+    #[test]
+    fn converts_lamports_to_usd() {
+        let mut price_update = price_update();
+        assert_eq!(lamports_to_usd(&price_update, 1_000_000_000), 150_000_000);
+        assert_eq!(lamports_to_usd(&price_update, 1_000), 150);
+        price_update.price_message.price = 150;
+        price_update.price_message.exponent = 0;
+        assert_eq!(lamports_to_usd(&price_update, 1_000_000_000), 150_000_000);
+        price_update.price_message.exponent = -60;
+        assert_eq!(lamports_to_usd(&price_update, 1_000_000_000), 0);
+    }
+
+    #[test]
+    fn fee_within_tolerance() {
+        let config = config();
+        let check = |fee, fee_usd| check_native_fee(&config, Some(&price_update()), fee, fee_usd, true, NOW);
+        assert!(check(10_000_000, 1_500_000).is_ok());
+        assert!(check(10_100_006, 1_500_000).is_ok());
+        assert_error(check(10_100_007, 1_500_000), PriceFeedError::NativeFeeMismatch);
+        assert_error(check(9_899_999, 1_500_000), PriceFeedError::NativeFeeMismatch);
+    }
+
+    #[test]
+    fn unusable_price_is_rejected() {
+        let config = config();
+        let check = |price_update: Option<&PriceUpdateV2>| check_native_fee(&config, price_update, 10_000_000, 1_500_000, true, NOW);
+        assert_error(check(None), PriceFeedError::MissingPriceFeed);
+
+        let mut partial = price_update();
+        partial.verification_level = VerificationLevel::Partial { num_signatures: 5 };
+        assert_error(check(Some(&partial)), PriceFeedError::UnverifiedPrice);
+
+        let mut negative = price_update();
+        negative.price_message.price = -1;
+        assert_error(check(Some(&negative)), PriceFeedError::InvalidPrice);
+
+        let mut stale = price_update();
+        stale.price_message.publish_time = NOW - 61;
+        assert_error(check(Some(&stale)), PriceFeedError::StalePrice);
+
+        let mut uncertain = price_update();
+        uncertain.price_message.conf = 75_000_001;
+        assert_error(check(Some(&uncertain)), PriceFeedError::PriceUncertain);
+    }
+
+    #[test]
+    fn skipped_for_usdc_fees_and_without_feed() {
+        assert!(check_native_fee(&config(), None, 10_000_000, 0, false, NOW).is_ok());
+        assert!(check_native_fee(&config(), None, 0, 0, true, NOW).is_ok());
+        assert!(check_native_fee(&Config::zeroed(), None, 10_000_000, 0, true, NOW).is_ok());
+    }
+}
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::TransferError,
+    events::VolumeLimitReached,
+    state::{
+        Config,
+        VolumeScope,
+        VolumeShard,
+        VolumeWindow,
+    },
+};
+
+impl VolumeWindow {
+    fn rolled(&self, now: i64, window: i64) -> VolumeWindow {
+        let start = now - now.rem_euclid(window);
+        if start == self.start {
+            *self
+        } else if start == self.start + window {
+            VolumeWindow { start, current: 0, previous: self.current }
+        } else {
+            VolumeWindow { start, current: 0, previous: 0 }
+        }
+    }
+
+    /// Volume over the `window` seconds up to `now`.
+    pub fn volume(&self, now: i64, window: i64) -> u64 {
+        if window <= 0 {
+            return 0;
+        }
+        let rolled = self.rolled(now, window);
+        let overlap = (window - (now - rolled.start)) as u128;
+        (rolled.previous as u128 * overlap / window as u128) as u64 + rolled.current
+    }
+
+    pub fn record(&mut self, now: i64, window: i64, amount: u64) {
+        if window <= 0 {
+            return;
+        }
+        *self = self.rolled(now, window);
+        self.current = self.current.saturating_add(amount);
+    }
+}
+
+/// Limits that apply to a transfer as (scope, window, limit). A zero limit is unlimited. Global and
+/// domain windows are the shard's, held to its share of the limit.
+fn limits<'a>(
+    config: &'a Config,
+    shard: &'a VolumeShard,
+    user_volume: &'a VolumeWindow,
+    destination_domain: u32,
+    limit_tier: u8,
+) -> Result<[(VolumeScope, &'a VolumeWindow, u64); 3]> {
+    let domain = destination_domain as usize;
+    require!(domain < config.domain_volume_limit.len(), TransferError::InvalidDestinationDomain);
+    let user_limit = *config.user_volume_limit.get(limit_tier as usize).ok_or(TransferError::InvalidLimitTier)?;
+    Ok([
+        (VolumeScope::Global, &shard.global_volume, VolumeShard::share(config.global_volume_limit)),
+        (VolumeScope::Domain, &shard.domain_volume[domain], VolumeShard::share(config.domain_volume_limit[domain])),
+        (VolumeScope::User, user_volume, user_limit),
+    ])
+}
+
+/// Fails if `amount` would push the global, destination domain or user volume over its limit.
+pub fn check_volume_limits(
+    config: &Config,
+    shard: &VolumeShard,
+    user_volume: &VolumeWindow,
+    destination_domain: u32,
+    limit_tier: u8,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    for (scope, volume, limit) in limits(config, shard, user_volume, destination_domain, limit_tier)? {
+        if limit > 0 && volume.volume(now, config.volume_window).saturating_add(amount) > limit {
+            msg!("{:?} volume limit exceeded", scope);
+            return Err(TransferError::VolumeLimitExceeded.into());
+        }
+    }
+    Ok(())
+}
+
+/// Checks and records `amount`, returning an event for every limit this transfer brought its
+/// window up to.
+#[allow(clippy::too_many_arguments)]
+pub fn record_volume(
+    config: &Config,
+    shard: &mut VolumeShard,
+    user_volume: &mut VolumeWindow,
+    user: Pubkey,
+    destination_domain: u32,
+    limit_tier: u8,
+    amount: u64,
+    now: i64,
+) -> Result<Vec<VolumeLimitReached>> {
+    check_volume_limits(config, shard, user_volume, destination_domain, limit_tier, amount, now)?;
+
+    let window = config.volume_window;
+    shard.global_volume.record(now, window, amount);
+    shard.domain_volume[destination_domain as usize].record(now, window, amount);
+    user_volume.record(now, window, amount);
+
+    Ok(limits(config, shard, user_volume, destination_domain, limit_tier)?
+        .into_iter()
+        .filter_map(|(scope, volume, limit)| {
+            let volume = volume.volume(now, window);
+            (limit > 0 && volume >= limit).then_some(VolumeLimitReached {
+                scope,
+                destination_domain,
+                user,
+                volume,
+                limit,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        state::{
+            MAX_DOMAINS,
+            VOLUME_LIMIT_TIERS,
+            VOLUME_SHARDS,
+        },
+        utils::error::assert_error,
+    };
+
+    const WINDOW: i64 = 100;
+
+    #[test]
+    fn previous_window_decays_with_overlap() {
+        let mut volume = VolumeWindow::default();
+        volume.record(1_000, WINDOW, 400);
+        assert_eq!(volume.volume(1_050, WINDOW), 400);
+        assert_eq!(volume.volume(1_125, WINDOW), 300);
+        assert_eq!(volume.volume(1_175, WINDOW), 100);
+        assert_eq!(volume.volume(1_200, WINDOW), 0);
+    }
+
+    #[test]
+    fn record_rolls_the_window() {
+        let mut volume = VolumeWindow::default();
+        volume.record(1_000, WINDOW, 400);
+        volume.record(1_150, WINDOW, 100);
+        assert_eq!((volume.previous, volume.current), (400, 100));
+        volume.record(1_450, WINDOW, 10);
+        assert_eq!((volume.previous, volume.current), (0, 10));
+    }
+
+    #[test]
+    fn zero_length_window_tracks_nothing() {
+        let mut volume = VolumeWindow::default();
+        volume.record(1_000, 0, 400);
+        assert_eq!(volume.current, 0);
+        assert_eq!(volume.volume(1_000, 0), 0);
+
+        let mut config = Config::zeroed();
+        config.global_volume_limit = 100 * VOLUME_SHARDS as u64;
+        let shard = VolumeShard::default();
+        assert!(check_volume_limits(&config, &shard, &volume, 0, 0, 100, 1_000).is_ok());
+        assert_error(check_volume_limits(&config, &shard, &volume, 0, 0, 101, 1_000), TransferError::VolumeLimitExceeded);
+    }
+
+    #[test]
+    fn limits_cover_every_scope() {
+        let mut config = Config::zeroed();
+        config.volume_window = WINDOW;
+        config.domain_volume_limit[3] = 500 * VOLUME_SHARDS as u64;
+        config.user_volume_limit[1] = 300;
+        let mut shard = VolumeShard::default();
+        let mut user_volume = VolumeWindow::default();
+
+        let reached = record_volume(&config, &mut shard, &mut user_volume, Pubkey::default(), 3, 1, 300, 1_000).unwrap();
+        assert_eq!(reached.len(), 1);
+        assert_eq!(reached[0].scope, VolumeScope::User);
+        assert_error(
+            check_volume_limits(&config, &shard, &user_volume, 3, 1, 1, 1_000),
+            TransferError::VolumeLimitExceeded,
+        );
+        assert!(check_volume_limits(&config, &shard, &user_volume, 3, 0, 200, 1_000).is_ok());
+        assert_error(
+            check_volume_limits(&config, &shard, &user_volume, 3, 0, 201, 1_000),
+            TransferError::VolumeLimitExceeded,
+        );
+        // another shard has its own share of the domain limit
+        assert!(check_volume_limits(&config, &VolumeShard::default(), &VolumeWindow::default(), 3, 0, 500, 1_000).is_ok());
+    }
+
+    #[test]
+    fn shards_split_limits_evenly() {
+        assert_eq!(VolumeShard::share(0), 0);
+        assert_eq!(VolumeShard::share(1), 1);
+        assert_eq!(VolumeShard::share(100 * VOLUME_SHARDS as u64 + 1), 100);
+        let shards = (0..=u8::MAX).map(|byte| VolumeShard::index(&Pubkey::new_from_array([byte; 32])) as usize);
+        assert!(shards.clone().all(|shard| shard < VOLUME_SHARDS));
+        assert_eq!(shards.max(), Some(VOLUME_SHARDS - 1));
+    }
+
+    #[test]
+    fn out_of_range_domain_and_tier_are_rejected() {
+        let config = Config::zeroed();
+        let shard = VolumeShard::default();
+        let volume = VolumeWindow::default();
+        assert_error(
+            check_volume_limits(&config, &shard, &volume, MAX_DOMAINS as u32, 0, 1, 0),
+            TransferError::InvalidDestinationDomain,
+        );
+        assert_error(
+            check_volume_limits(&config, &shard, &volume, 0, VOLUME_LIMIT_TIERS as u8, 1, 0),
+            TransferError::InvalidLimitTier,
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::{
+    errors::TransferError,
+    state::{
+        ChainFamily,
+        Config,
+    },
+};
+
+/// Fails if `recipient` is not a valid address on the chain family of `destination_domain`, or
+/// the domain has no registered chain family. A Solana recipient must be the associated token
+/// account of `solana_owner` for `mint`.
+pub fn validate_recipient(
+    config: &Config,
+    destination_domain: u32,
+    recipient: &[u8; 32],
+    solana_owner: &[u8; 32],
+    mint: &Pubkey,
+) -> Result<()> {
+    require!(*recipient != [0; 32], TransferError::InvalidRecipient);
+    let family = config.chain_family.get(destination_domain as usize).copied().unwrap_or_default();
+    match family {
+        ChainFamily::Unknown => return err!(TransferError::UnknownChainFamily),
+        ChainFamily::Evm | ChainFamily::Noble => {
+            require!(recipient[..12] == [0; 12], TransferError::InvalidRecipient);
+        }
+        ChainFamily::Solana => {
+            require!(*solana_owner != [0; 32], TransferError::InvalidSolanaOwner);
+            let token_account = get_associated_token_address(&Pubkey::new_from_array(*solana_owner), mint);
+            require!(token_account.to_bytes() == *recipient, TransferError::InvalidRecipient);
+        }
+        ChainFamily::Aptos | ChainFamily::Sui => {
+            // 0x0 to 0xf are reserved for the framework and system objects on both chains
+            require!(recipient[..31] != [0; 31] || recipient[31] > 0xf, TransferError::InvalidRecipient);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::error::assert_error;
+
+    const MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+    fn evm_address() -> [u8; 32] {
+        let mut recipient = [0; 32];
+        recipient[12..].copy_from_slice(&[0xab; 20]);
+        recipient
+    }
+
+    fn config() -> Config {
+        let mut config = Config::zeroed();
+        config.chain_family[0] = ChainFamily::Evm;
+        config.chain_family[4] = ChainFamily::Noble;
+        config.chain_family[5] = ChainFamily::Solana;
+        config.chain_family[8] = ChainFamily::Sui;
+        config.chain_family[9] = ChainFamily::Aptos;
+        config
+    }
+
+    #[test]
+    fn zero_recipient_is_rejected() {
+        for domain in [0, 5, 8, 9, 31] {
+            assert_error(validate_recipient(&config(), domain, &[0; 32], &[1; 32], &MINT), TransferError::InvalidRecipient);
+        }
+    }
+
+    #[test]
+    fn evm_address_must_be_left_padded() {
+        let config = config();
+        assert!(validate_recipient(&config, 0, &evm_address(), &[0; 32], &MINT).is_ok());
+        assert!(validate_recipient(&config, 4, &evm_address(), &[0; 32], &MINT).is_ok());
+        for byte in 0..12 {
+            let mut recipient = evm_address();
+            recipient[byte] = 1;
+            assert_error(validate_recipient(&config, 0, &recipient, &[0; 32], &MINT), TransferError::InvalidRecipient);
+            assert_error(validate_recipient(&config, 4, &recipient, &[0; 32], &MINT), TransferError::InvalidRecipient);
+        }
+    }
+
+    #[test]
+    fn solana_recipient_must_be_owner_ata() {
+        let config = config();
+        let owner = Pubkey::new_from_array([2; 32]);
+        let ata = get_associated_token_address(&owner, &MINT).to_bytes();
+        assert!(validate_recipient(&config, 5, &ata, &owner.to_bytes(), &MINT).is_ok());
+        assert_error(validate_recipient(&config, 5, &ata, &[0; 32], &MINT), TransferError::InvalidSolanaOwner);
+        assert_error(validate_recipient(&config, 5, &ata, &[3; 32], &MINT), TransferError::InvalidRecipient);
+        assert_error(validate_recipient(&config, 5, &[1; 32], &owner.to_bytes(), &MINT), TransferError::InvalidRecipient);
+        assert_error(
+            validate_recipient(&config, 5, &ata, &owner.to_bytes(), &Pubkey::new_from_array([4; 32])),
+            TransferError::InvalidRecipient,
+        );
+    }
+
+    #[test]
+    fn move_chains_reject_reserved_addresses() {
+        let config = config();
+        for domain in [8, 9] {
+            assert!(validate_recipient(&config, domain, &[0xff; 32], &[0; 32], &MINT).is_ok());
+            let mut recipient = [0; 32];
+            for last in 1..=0xf {
+                recipient[31] = last;
+                assert_error(validate_recipient(&config, domain, &recipient, &[0; 32], &MINT), TransferError::InvalidRecipient);
+            }
+            recipient[31] = 0x10;
+            assert!(validate_recipient(&config, domain, &recipient, &[0; 32], &MINT).is_ok());
+        }
+    }
+
+    #[test]
+    fn unknown_family_is_rejected() {
+        let config = config();
+        assert_error(validate_recipient(&config, 31, &[0xff; 32], &[0; 32], &MINT), TransferError::UnknownChainFamily);
+        assert_error(validate_recipient(&config, u32::MAX, &[0xff; 32], &[0; 32], &MINT), TransferError::UnknownChainFamily);
+    }
+}